@@ -0,0 +1,62 @@
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+#[redis(rename_all = "snake_case")]
+enum Plan {
+    Free,
+    Pro,
+}
+
+#[derive(FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+#[redis(key = "account:{id}", repository)]
+struct Account {
+    id: u64,
+    owner: String,
+    plan: Plan,
+    nickname: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> redis::RedisResult<()> {
+    println!("🚀 Redis Derive Async Repository Example");
+    println!("========================================");
+
+    let client = redis::Client::open("redis://127.0.0.1:6379/")?;
+    let mut con = client.get_multiplexed_async_connection().await?;
+
+    // Test 1: Save and load the whole struct
+    println!("\n💾 Testing save_async / load_async...");
+    let account = Account {
+        id: 1,
+        owner: "jane".to_string(),
+        plan: Plan::Free,
+        nickname: Some("jj".to_string()),
+    };
+    account.save_async(&mut con).await?;
+
+    let loaded = Account::load_async(&mut con, account.key()).await?;
+    println!("   ✓ Loaded: {:?}", loaded);
+    assert_eq!(loaded.as_ref(), Some(&account));
+
+    // Test 2: Single field access
+    println!("\n📎 Testing per-field helpers...");
+    Account::save_plan_async(&mut con, account.key(), &Plan::Pro).await?;
+    let plan = Account::load_plan_async(&mut con, account.key()).await?;
+    println!("   ✓ Plan: {:?}", plan);
+    assert_eq!(plan, Some(Plan::Pro));
+
+    Account::save_nickname_async(&mut con, account.key(), &None).await?;
+    let nickname = Account::load_nickname_async(&mut con, account.key()).await?;
+    println!("   ✓ Nickname after clearing: {:?}", nickname);
+    assert_eq!(nickname, None);
+
+    // Test 3: Exists and delete
+    println!("\n🗑️  Testing exists_async / delete_async...");
+    assert!(Account::exists_async(&mut con, account.key()).await?);
+    assert!(Account::delete_async(&mut con, account.key()).await?);
+    assert!(!Account::exists_async(&mut con, account.key()).await?);
+    assert_eq!(Account::load_async(&mut con, account.key()).await?, None);
+
+    println!("\n🎉 All async repository operations work!");
+    Ok(())
+}
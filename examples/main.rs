@@ -18,6 +18,7 @@ enum UserRole {
 }
 
 #[derive(FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+#[redis(key = "user:{id}", repository)]
 struct User {
     id: u64,
     username: String,
@@ -45,17 +46,11 @@ fn main() -> redis::RedisResult<()> {
         role: UserRole::Administrator,
     };
 
-    // Store the struct as a Redis hash using individual field sets
-    con.hset::<_, _, _, ()>("user:12345", "id", user.id)?;
-    con.hset::<_, _, _, ()>("user:12345", "username", &user.username)?;
-    con.hset::<_, _, _, ()>("user:12345", "email", &user.email)?;
-    con.hset::<_, _, _, ()>("user:12345", "active", user.active)?;
-    con.hset::<_, _, _, ()>("user:12345", "favorite_color", &user.favorite_color)?;
-    con.hset::<_, _, _, ()>("user:12345", "role", &user.role)?;
-
+    // Store the struct as a Redis hash with a single HSET
+    user.save(&mut con)?;
 
     // Retrieve the struct from Redis
-    let retrieved_user: User = con.hgetall("user:12345")?;
+    let retrieved_user = User::load(&mut con, user.key())?.expect("user was just saved");
     
     println!("   ✓ Original:  {:?}", user);
     println!("   ✓ Retrieved: {:?}", retrieved_user);
@@ -86,7 +81,7 @@ fn main() -> redis::RedisResult<()> {
 
     // Test 3: View raw data
     println!("\n🔍 Inspecting stored data...");
-    let hash_data: HashMap<String, String> = con.hgetall("user:12345")?;
+    let hash_data: HashMap<String, String> = con.hgetall(user.key())?;
     println!("   Raw hash data:");
     for (key, value) in &hash_data {
         println!("     {} = {}", key, value);
@@ -99,8 +94,8 @@ fn main() -> redis::RedisResult<()> {
 
     // Test 4: Individual field access
     println!("\n📎 Testing individual field access...");
-    let username: String = con.hget("user:12345", "username")?;
-    let role_str: String = con.hget("user:12345", "role")?;
+    let username: String = con.hget(user.key(), "username")?;
+    let role_str: String = con.hget(user.key(), "role")?;
     println!("   ✓ Username: {}", username);
     println!("   ✓ Role: {}", role_str);
     assert_eq!(username, "john_doe");
@@ -1,43 +1,65 @@
-use crate::util::{self, ParsedAttributeMap};
-use quote::quote;
-use syn::{DataEnum, Fields, Ident};
+use crate::util::{self, DefaultValue, FieldAttributes, ParsedAttributeMap, VariantValue};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::{DataEnum, Fields, Generics, Ident, Member, Type};
 
 pub fn derive_to_redis_enum(
     data_enum: DataEnum,
     type_ident: Ident,
+    generics: Generics,
     attrs: ParsedAttributeMap,
-) -> proc_macro::TokenStream {
-    // Check if all variants are unit variants (fieldless)
-    let is_unit_enum = data_enum.variants.iter().all(|v| v.fields == Fields::Unit);
-
-    if !is_unit_enum {
-        panic!("ToRedisArgs can only be derived for enums with unit variants (no fields). Consider using a struct with an enum field instead.");
+) -> syn::Result<TokenStream> {
+    let variants = parse_variants(&data_enum, &attrs)?;
+    check_scalar_repr(&variants, &attrs)?;
+    let string_impls = if attrs.strings {
+        Some(string_impls(&variants, &type_ident, &generics, &attrs)?)
+    } else {
+        None
+    };
+    if is_hash_enum(&variants, &attrs) {
+        return derive_to_redis_hash_enum(&variants, &type_ident, generics, &attrs);
     }
 
-    let variant_data: Vec<_> = data_enum
-        .variants
+    let variant_matches: Vec<_> = variants
         .iter()
         .map(|variant| {
-            let variant_ident = &variant.ident;
-            let variant_name = util::transform_variant_name(
-                &variant_ident.to_string(),
-                attrs.rename_all.as_ref(),
-            );
-            (variant_ident, variant_name)
-        })
-        .collect();
-
-    let variant_matches: Vec<_> = variant_data
-        .iter()
-        .map(|(variant_ident, variant_name)| {
-            quote! {
-                #type_ident::#variant_ident => out.write_arg(#variant_name.as_bytes()),
+            let variant_ident = variant.ident;
+            let variant_name = &variant.name;
+            if variant.newtype {
+                // The `other` variant writes back the value it was read from.
+                quote! {
+                    Self::#variant_ident(raw) => redis::ToRedisArgs::write_redis_args(raw, out),
+                }
+            } else if let Some(value) = &variant.value {
+                let value = value.to_expr();
+                quote! {
+                    Self::#variant_ident => redis::ToRedisArgs::write_redis_args(&#value, out),
+                }
+            } else if let Some(repr) = &attrs.repr {
+                quote! {
+                    Self::#variant_ident => {
+                        redis::ToRedisArgs::write_redis_args(&(Self::#variant_ident as #repr), out)
+                    }
+                }
+            } else {
+                quote! {
+                    Self::#variant_ident => out.write_arg(#variant_name.as_bytes()),
+                }
             }
         })
         .collect();
 
+    let generics = util::add_trait_bounds(
+        generics,
+        &[],
+        quote!(redis::ToRedisArgs),
+        attrs.bound.as_ref(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let to_redis_impl = quote! {
-        impl redis::ToRedisArgs for #type_ident {
+        impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
             fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
                 match self {
                     #(#variant_matches)*
@@ -48,101 +70,79 @@ pub fn derive_to_redis_enum(
                 1 // Enums are always single-argument (the variant name)
             }
         }
+
+        #string_impls
     };
 
-    to_redis_impl.into()
+    Ok(to_redis_impl)
 }
 
 pub fn derive_from_redis_enum(
     data_enum: DataEnum,
     type_ident: Ident,
+    generics: Generics,
     attrs: ParsedAttributeMap,
-) -> proc_macro::TokenStream {
-    // Check if all variants are unit variants (fieldless)
-    let is_unit_enum = data_enum.variants.iter().all(|v| v.fields == Fields::Unit);
-
-    if !is_unit_enum {
-        panic!("FromRedisValue can only be derived for enums with unit variants (no fields). Consider using a struct with an enum field instead.");
+) -> syn::Result<TokenStream> {
+    let variants = parse_variants(&data_enum, &attrs)?;
+    check_scalar_repr(&variants, &attrs)?;
+    if is_hash_enum(&variants, &attrs) {
+        return derive_from_redis_hash_enum(&variants, &type_ident, generics, &attrs);
+    }
+    if attrs.repr.is_some() {
+        return derive_from_redis_int_enum(&variants, &type_ident, generics, &attrs);
     }
 
-    let variant_data: Vec<_> = data_enum
-        .variants
-        .iter()
-        .map(|variant| {
-            let variant_ident = &variant.ident;
-            let variant_name = util::transform_variant_name(
-                &variant_ident.to_string(),
-                attrs.rename_all.as_ref(),
-            );
-            (variant_ident, variant_name)
-        })
-        .collect();
+    let parse_string_to_enum = name_parser(&variants, &type_ident, &attrs);
 
-    let match_arms: Vec<_> = variant_data
-        .iter()
-        .map(|(variant_ident, variant_name)| {
-            quote! {
-                #variant_name => Ok(#type_ident::#variant_ident),
+    // Mapped values read back from their string form, which `match_name` knows.
+    let has_values = variants.iter().any(|variant| variant.value.is_some());
+    let value_arms = has_values.then(|| {
+        quote! {
+            redis::Value::Int(n) => parse_str(&n.to_string()),
+            redis::Value::Boolean(b) => parse_str(if *b { "1" } else { "0" }),
+            redis::Value::Double(d) if d.is_finite() && d.fract() == 0.0 => {
+                parse_str(&(*d as i64).to_string())
             }
-        })
-        .collect();
-
-    let variant_names: Vec<&str> = variant_data.iter().map(|(_, name)| name.as_str()).collect();
-    let variant_list = variant_names.join(", ");
-
-    // Helper function to create error for unknown variants
-    let create_unknown_variant_error = quote! {
-        |unknown: &str| -> redis::RedisError {
-            redis::RedisError::from((
-                redis::ErrorKind::TypeError,
-                "Unknown enum variant",
-                format!(
-                    "Unknown variant '{}' for {}. Valid variants: [{}]",
-                    unknown,
-                    stringify!(#type_ident),
-                    #variant_list
-                ),
-            ))
         }
-    };
+    });
+    let kind = util::value_kind(quote!(v));
 
-    // Helper function to parse string to enum
-    let parse_string_to_enum = quote! {
-        |s: &str| -> redis::RedisResult<#type_ident> {
-            let create_error = #create_unknown_variant_error;
-            match s {
-                #(#match_arms)*
-                unknown => Err(create_error(unknown))
-            }
-        }
-    };
+    let generics = util::add_trait_bounds(
+        generics,
+        &[],
+        quote!(redis::FromRedisValue),
+        attrs.bound.as_ref(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let from_redis_impl = quote! {
-        impl redis::FromRedisValue for #type_ident {
+        impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
             fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
                 let parse_str = #parse_string_to_enum;
 
                 match v {
                     // Handle binary string data (most common for stored values)
                     redis::Value::BulkString(data) => {
-                        let s = String::from_utf8(data.clone())
+                        let s = std::str::from_utf8(data)
                             .map_err(|e| redis::RedisError::from((
                                 redis::ErrorKind::TypeError,
                                 "Invalid UTF-8 in enum value",
                                 e.to_string(),
                             )))?;
-                        parse_str(&s)
+                        parse_str(s)
                     }
                     
                     // Handle simple string responses
                     redis::Value::SimpleString(s) => {
-                        parse_str(s)
+                        parse_str(s.as_str())
                     }
                     
                     // Handle verbatim strings (Redis 6+ feature)
                     redis::Value::VerbatimString { text, .. } => {
-                        parse_str(text)
+                        parse_str(text.as_str())
                     }
+
+                    #value_arms
                     
                     // Handle nil values with clear error
                     redis::Value::Nil => {
@@ -159,8 +159,9 @@ pub fn derive_from_redis_enum(
                             redis::ErrorKind::TypeError,
                             "Cannot deserialize enum from Redis value type",
                             format!(
-                                "Expected string value for {}, got unsupported Redis value type",
-                                stringify!(#type_ident)
+                                "Expected string value for {}, got {}",
+                                stringify!(#type_ident),
+                                #kind
                             ),
                         )))
                     }
@@ -169,5 +170,1111 @@ pub fn derive_from_redis_enum(
         }
     };
 
-    from_redis_impl.into()
-}
\ No newline at end of file
+    Ok(from_redis_impl)
+}
+
+/// `#[redis(strings)]`: `VARIANTS`, `as_redis_str`, `Display` and `FromStr` built from the
+/// same names the `ToRedisArgs` impl writes and the `FromRedisValue` impl reads.
+fn string_impls(
+    variants: &[Variant],
+    type_ident: &Ident,
+    generics: &Generics,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    if let Some(variant) = variants.iter().find(|variant| !variant.unit) {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "`strings` requires an enum of unit variants",
+        ));
+    }
+    if attrs.repr.is_some() || attrs.tag.is_some() || attrs.untagged {
+        return Err(syn::Error::new(
+            attrs.span("strings"),
+            "`strings` cannot be combined with `repr = \"int\"`, `tag` or `untagged`",
+        ));
+    }
+    if let Some(variant) = variants.iter().find(|variant| variant.value.is_some()) {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "`strings` cannot be combined with `value`",
+        ));
+    }
+
+    let idents: Vec<_> = variants.iter().map(|variant| variant.ident).collect();
+    let names: Vec<_> = variants.iter().map(|variant| &variant.name).collect();
+    let parse_str = name_parser(variants, type_ident, attrs);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #type_ident #ty_generics #where_clause {
+            /// Names of all variants, in declaration order, as written to Redis.
+            #[allow(dead_code)]
+            pub const VARIANTS: &'static [&'static str] = &[#(#names),*];
+
+            /// The name this variant is written to Redis as.
+            #[allow(dead_code)]
+            pub fn as_redis_str(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #names,)*
+                }
+            }
+        }
+
+        impl #impl_generics std::fmt::Display for #type_ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_redis_str())
+            }
+        }
+
+        impl #impl_generics std::str::FromStr for #type_ident #ty_generics #where_clause {
+            type Err = redis::RedisError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let parse_str = #parse_str;
+                parse_str(s)
+            }
+        }
+    })
+}
+
+/// `repr = "int"` and variant `value`s replace the name of an enum stored as a single
+/// value, and the discriminant only exists on fieldless enums.
+fn check_scalar_repr(variants: &[Variant], attrs: &ParsedAttributeMap) -> syn::Result<()> {
+    if let Some(variant) = variants.iter().find(|variant| variant.value.is_some()) {
+        if attrs.repr.is_some() {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "`value` cannot be combined with `repr = \"int\"`",
+            ));
+        }
+        if is_hash_enum(variants, attrs) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "`value` requires an enum of unit variants without `tag` or `untagged`",
+            ));
+        }
+    }
+    if attrs.repr.is_none() {
+        return Ok(());
+    }
+    if attrs.tag.is_some() || attrs.untagged {
+        return Err(syn::Error::new(
+            attrs.span("repr"),
+            "`repr = \"int\"` cannot be combined with `tag` or `untagged`",
+        ));
+    }
+    match variants.iter().find(|variant| !variant.unit) {
+        Some(variant) => Err(syn::Error::new(
+            variant.ident.span(),
+            "`repr = \"int\"` requires an enum of unit variants",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `FromRedisValue` for `repr = "int"`: read an integer code from an integer reply, a
+/// numeric string or a whole RESP3 double, and map it back to its variant.
+fn derive_from_redis_int_enum(
+    variants: &[Variant],
+    type_ident: &Ident,
+    generics: Generics,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let idents: Vec<_> = variants
+        .iter()
+        .filter(|variant| !variant.other)
+        .map(|variant| variant.ident)
+        .collect();
+    let fallback = match variants.iter().find(|variant| variant.other) {
+        Some(Variant { ident, .. }) => quote!(Ok(Self::#ident)),
+        None => quote! {
+            let valid: &[i128] = &[#(Self::#idents as i128),*];
+            Err(redis::RedisError::from((
+                redis::ErrorKind::TypeError,
+                "Unknown enum discriminant",
+                format!(
+                    "Unknown code {} for {}. Valid codes: {:?}",
+                    code,
+                    stringify!(#type_ident),
+                    valid
+                ),
+            )))
+        },
+    };
+
+    let kind = util::value_kind(quote!(v));
+
+    let generics = util::add_trait_bounds(
+        generics,
+        &[],
+        quote!(redis::FromRedisValue),
+        attrs.bound.as_ref(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                let parse_code = |s: &str| -> redis::RedisResult<i128> {
+                    s.trim().parse().map_err(|_| redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Invalid enum discriminant",
+                        format!(
+                            "Expected integer code for {}, got '{}'",
+                            stringify!(#type_ident),
+                            s
+                        ),
+                    )))
+                };
+
+                let code: i128 = match v {
+                    redis::Value::Int(n) => i128::from(*n),
+                    redis::Value::BulkString(data) => {
+                        parse_code(&String::from_utf8_lossy(data))?
+                    }
+                    redis::Value::SimpleString(s) => parse_code(s)?,
+                    redis::Value::VerbatimString { text, .. } => parse_code(text)?,
+                    redis::Value::Double(d) if d.is_finite() && d.fract() == 0.0 => *d as i128,
+                    redis::Value::Double(d) => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Invalid enum discriminant",
+                            format!(
+                                "Expected integer code for {}, got {}",
+                                stringify!(#type_ident),
+                                d
+                            ),
+                        )));
+                    }
+                    redis::Value::Nil => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Cannot deserialize enum from nil value",
+                            format!("Expected integer code for {}, got nil", stringify!(#type_ident)),
+                        )));
+                    }
+                    _ => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Cannot deserialize enum from Redis value type",
+                            format!(
+                                "Expected integer code for {}, got {}",
+                                stringify!(#type_ident),
+                                #kind
+                            ),
+                        )));
+                    }
+                };
+
+                #(
+                    if code == Self::#idents as i128 {
+                        return Ok(Self::#idents);
+                    }
+                )*
+                #fallback
+            }
+        }
+    })
+}
+
+/// Closure expression parsing a variant name into `Self`, shared by `FromRedisValue` and
+/// the `FromStr` generated for `strings`.
+fn name_parser(
+    variants: &[Variant],
+    type_ident: &Ident,
+    attrs: &ParsedAttributeMap,
+) -> TokenStream {
+    let name_arms: Vec<_> = variants
+        .iter()
+        .filter(|variant| !variant.newtype)
+        .map(|variant| {
+            let variant_ident = variant.ident;
+            (variant.names().collect(), quote!(Ok(Self::#variant_ident)))
+        })
+        .collect();
+
+    let variant_names: Vec<&str> = variants
+        .iter()
+        .filter(|variant| !variant.newtype)
+        .flat_map(Variant::names)
+        .map(String::as_str)
+        .collect();
+    let variant_list = variant_names.join(", ");
+
+    // Helper function to create error for unknown variants
+    let create_unknown_variant_error = quote! {
+        |unknown: &str| -> redis::RedisError {
+            redis::RedisError::from((
+                redis::ErrorKind::TypeError,
+                "Unknown enum variant",
+                format!(
+                    "Unknown variant '{}' for {}. Valid variants: [{}]",
+                    unknown,
+                    stringify!(#type_ident),
+                    #variant_list
+                ),
+            ))
+        }
+    };
+
+    let fallback = match variants.iter().find(|variant| variant.other) {
+        Some(Variant {
+            ident,
+            newtype: true,
+            ..
+        }) => Fallback::Named(quote!(Ok(Self::#ident(unknown.into())))),
+        Some(Variant { ident, .. }) => Fallback::Wildcard(quote!(Ok(Self::#ident))),
+        None => Fallback::Named(quote! {
+            let create_error = #create_unknown_variant_error;
+            Err(create_error(unknown))
+        }),
+    };
+    let match_name = match_name(quote!(s), name_arms, fallback, attrs);
+
+    // Helper function to parse string to enum
+    quote! {
+        |s: &str| -> redis::RedisResult<Self> {
+            #match_name
+        }
+    }
+}
+
+/// What a generated name match does with a name no variant claims.
+enum Fallback {
+    /// Evaluated with the unmatched name bound to `unknown`.
+    Named(TokenStream),
+    /// Evaluated without looking at the name.
+    Wildcard(TokenStream),
+}
+
+/// Match the `&str` expression `value` against the names of each arm, exactly or, with
+/// `case_insensitive`, ASCII-case-insensitively without allocating. With `trim`,
+/// surrounding whitespace is ignored; `unknown` is still the untrimmed value.
+fn match_name(
+    value: TokenStream,
+    arms: Vec<(Vec<&String>, TokenStream)>,
+    fallback: Fallback,
+    attrs: &ParsedAttributeMap,
+) -> TokenStream {
+    let name = if attrs.trim {
+        quote!(#value.trim())
+    } else {
+        value.clone()
+    };
+    let fallback = match fallback {
+        Fallback::Named(body) => quote! {
+            {
+                let unknown: &str = #value;
+                #body
+            }
+        },
+        Fallback::Wildcard(body) => body,
+    };
+    let (names, bodies): (Vec<_>, Vec<_>) = arms.into_iter().unzip();
+
+    if attrs.case_insensitive {
+        let conditions = names
+            .iter()
+            .map(|names| quote!(#(name.eq_ignore_ascii_case(#names))||*));
+        quote! {
+            {
+                let name: &str = #name;
+                #(if #conditions { #bodies } else)* { #fallback }
+            }
+        }
+    } else {
+        let names = names.iter().map(|names| quote!(#(#names)|*));
+        quote! {
+            match #name {
+                #(#names => #bodies,)*
+                _ => #fallback,
+            }
+        }
+    }
+}
+
+/// Enums with tuple or struct variants (other than an `other` newtype), or with
+/// `tag`/`untagged`, are stored as hashes instead of a single variant name.
+fn is_hash_enum(variants: &[Variant], attrs: &ParsedAttributeMap) -> bool {
+    attrs.tag.is_some()
+        || attrs.untagged
+        || variants.iter().any(|v| !(v.unit || (v.other && v.newtype)))
+}
+
+/// How the variant of a hash enum is recorded alongside its payload.
+enum Tagging<'a> {
+    /// Payload fields keyed by the variant name (`circle.radius`); the default.
+    External,
+    /// `#[redis(tag = "type")]`: the variant name in the `tag` field, payload fields
+    /// alongside it.
+    Internal { tag: &'a str },
+    /// `#[redis(tag = "t", content = "c")]`: the variant name in the `tag` field,
+    /// payload fields keyed by `content` (`c.radius`).
+    Adjacent { tag: &'a str, content: &'a str },
+    /// `#[redis(untagged)]`: payload fields only; reading tries each variant in order.
+    Untagged,
+}
+
+impl<'a> Tagging<'a> {
+    fn new(attrs: &'a ParsedAttributeMap) -> Self {
+        match (&attrs.tag, &attrs.content) {
+            (Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+            (Some(tag), None) => Tagging::Internal { tag },
+            (None, _) if attrs.untagged => Tagging::Untagged,
+            (None, _) => Tagging::External,
+        }
+    }
+
+    fn tag(&self) -> Option<&'a str> {
+        match self {
+            Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => Some(tag),
+            Tagging::External | Tagging::Untagged => None,
+        }
+    }
+
+    /// In a hash only a unit variant can be `other`, and only when there is a tag
+    /// field to fall back on.
+    fn check_other(&self, variants: &[Variant]) -> syn::Result<()> {
+        match variants.iter().find(|variant| variant.other) {
+            Some(other) if !other.unit => Err(syn::Error::new(
+                other.ident.span(),
+                "`other` on a newtype variant requires an enum of unit variants",
+            )),
+            Some(other) if self.tag().is_none() => Err(syn::Error::new(
+                other.ident.span(),
+                "`other` on an enum with data requires `tag`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Without a field to nest it under, a newtype payload is written as the variant's
+    /// own fields, so a scalar would leave a lone value among the field/value pairs.
+    fn check_newtypes(&self, variants: &[Variant]) -> syn::Result<()> {
+        let nested = match self {
+            Tagging::Internal { .. } => "internally tagged",
+            Tagging::Untagged => "untagged",
+            Tagging::External | Tagging::Adjacent { .. } => return Ok(()),
+        };
+        let scalar = variants
+            .iter()
+            .filter(|variant| variant.newtype)
+            .flat_map(|variant| &variant.fields)
+            .find(|field| util::is_scalar(field.ty));
+        match scalar {
+            Some(field) => Err(syn::Error::new_spanned(
+                field.ty,
+                format!(
+                    "a newtype variant of an {nested} enum must wrap a type stored as fields, such as a struct"
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Hash field the payload of `variant_name` is nested under, if any.
+    fn prefix<'b>(&'b self, variant_name: &'b str) -> Option<&'b str> {
+        match self {
+            Tagging::External => Some(variant_name),
+            Tagging::Adjacent { content, .. } => Some(content),
+            Tagging::Internal { .. } | Tagging::Untagged => None,
+        }
+    }
+}
+
+struct Variant<'a> {
+    ident: &'a Ident,
+    name: String,
+    /// Extra names accepted when reading.
+    aliases: Vec<String>,
+    /// Declared without fields.
+    unit: bool,
+    /// A single unnamed field stored as the payload itself rather than as `0`.
+    newtype: bool,
+    /// `#[redis(other)]`: takes any value no other variant matches.
+    other: bool,
+    /// `#[redis(value = ...)]`: written instead of the name.
+    value: Option<VariantValue>,
+    /// How `value` reads back as a string, accepted alongside the names.
+    value_name: Option<String>,
+    fields: Vec<VariantField<'a>>,
+}
+
+struct VariantField<'a> {
+    member: Member,
+    binding: Ident,
+    name: String,
+    ty: &'a Type,
+    attrs: FieldAttributes,
+}
+
+fn parse_variants<'a>(
+    data_enum: &'a DataEnum,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<Vec<Variant<'a>>> {
+    let variants = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let fields = variant
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let field_attrs = util::parse_field_attributes(&field.attrs)?;
+                    if field_attrs.expire.is_some() || field_attrs.expire_at.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "field expiry is not supported on enum variant fields",
+                        ));
+                    }
+                    let unsupported = if field_attrs.flatten {
+                        Some("flatten")
+                    } else if field_attrs.json {
+                        Some("json")
+                    } else if field_attrs.as_string {
+                        Some("as_string")
+                    } else if field_attrs.serialize_with.is_some() {
+                        Some("serialize_with")
+                    } else if field_attrs.deserialize_with.is_some() {
+                        Some("deserialize_with")
+                    } else {
+                        None
+                    };
+                    if let Some(attr) = unsupported {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            format!("`{attr}` is not supported on enum variant fields"),
+                        ));
+                    }
+                    let (member, default_name) = match &field.ident {
+                        Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+                        None => (Member::Unnamed(index.into()), index.to_string()),
+                    };
+                    Ok(VariantField {
+                        member,
+                        binding: format_ident!("__field{}", index),
+                        name: field_attrs.rename.clone().unwrap_or(default_name),
+                        ty: &field.ty,
+                        attrs: field_attrs,
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            let variant_attrs = util::parse_variant_attributes(&variant.attrs)?;
+            Ok(Variant {
+                ident: &variant.ident,
+                name: variant_attrs.rename.unwrap_or_else(|| {
+                    util::transform_variant_name(
+                        &variant.ident.to_string(),
+                        attrs.rename_all.as_ref(),
+                    )
+                }),
+                aliases: variant_attrs.aliases,
+                unit: variant.fields == Fields::Unit,
+                newtype: matches!(variant.fields, Fields::Unnamed(_)) && fields.len() == 1,
+                other: variant_attrs.other,
+                value: variant_attrs.value,
+                value_name: variant_attrs.value.map(|value| value.code().to_string()),
+                fields,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut others = variants.iter().filter(|variant| variant.other);
+    if let (Some(_), Some(extra)) = (others.next(), others.next()) {
+        return Err(syn::Error::new(
+            extra.ident.span(),
+            "only one variant can be marked `other`",
+        ));
+    }
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| variant.value.is_some() && !variant.unit)
+    {
+        return Err(syn::Error::new(
+            variant.ident.span(),
+            "`value` requires a unit variant",
+        ));
+    }
+    if let Some(other) = variants
+        .iter()
+        .find(|variant| variant.other && !variant.unit && !variant.newtype)
+    {
+        return Err(syn::Error::new(
+            other.ident.span(),
+            "`other` requires a unit variant or a newtype variant such as `Unknown(String)`",
+        ));
+    }
+
+    // With `case_insensitive`, names differing only in case would be ambiguous.
+    let normalize = |name: &String| {
+        if attrs.case_insensitive {
+            name.to_ascii_lowercase()
+        } else {
+            name.clone()
+        }
+    };
+    let mut codes = HashSet::new();
+    for variant in &variants {
+        if let Some(value) = variant.value.filter(|value| !codes.insert(value.code())) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!("variant value `{}` is used more than once", value.code()),
+            ));
+        }
+    }
+    let mut seen = HashSet::new();
+    for variant in &variants {
+        if let Some(name) = variant.names().find(|name| !seen.insert(normalize(name))) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!("variant name `{name}` is used more than once"),
+            ));
+        }
+    }
+
+    Ok(variants)
+}
+
+/// What the generated `FromRedisValue` reads, collected while building decoders.
+#[derive(Default)]
+struct DecodeBounds<'a> {
+    field_types: Vec<&'a Type>,
+    default_types: Vec<&'a Type>,
+    /// Whether any decoder looks at `fields_map`.
+    fields_map: bool,
+}
+
+impl<'a> Variant<'a> {
+    /// The name the variant is written as, followed by its aliases and the string form
+    /// of its `value`.
+    fn names(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .chain(&self.value_name)
+    }
+
+    /// Expression decoding this variant from `v`/`fields_map`, with its payload nested
+    /// under `prefix`.
+    fn decoder(
+        &self,
+        prefix: Option<&str>,
+        tagging: &Tagging,
+        reads: &mut DecodeBounds<'a>,
+    ) -> TokenStream {
+        let variant_ident = self.ident;
+        let mut field_inits = Vec::new();
+        let mut uses_fields_map = false;
+
+        for field in &self.fields {
+            let member = &field.member;
+            let fallback = match &field.attrs.default {
+                Some(default) => {
+                    if let DefaultValue::Trait = default {
+                        reads.default_types.push(field.ty);
+                    }
+                    Some(default.to_expr())
+                }
+                None if field.attrs.skips_deserializing() => {
+                    reads.default_types.push(field.ty);
+                    Some(DefaultValue::Trait.to_expr())
+                }
+                None if util::is_option(field.ty) => Some(quote!(None)),
+                None => None,
+            };
+
+            if field.attrs.skips_deserializing() {
+                field_inits.push(quote! { #member: #fallback, });
+                continue;
+            }
+            reads.field_types.push(field.ty);
+
+            if self.newtype && prefix.is_none() {
+                field_inits.push(quote! {
+                    #member: redis::FromRedisValue::from_redis_value(v)?,
+                });
+                continue;
+            }
+
+            let name = self.field_key(field, prefix);
+            uses_fields_map = true;
+            let missing = match (fallback, self.newtype) {
+                (Some(fallback), _) => fallback,
+                // The payload wrote no field/value pairs, or a marker in their place.
+                (None, true) => quote! {
+                    redis::FromRedisValue::from_redis_value(&redis::Value::Map(Vec::new()))?
+                },
+                (None, false) => quote! {
+                    return Err(redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Missing required field",
+                        #name.to_string(),
+                    )))
+                },
+            };
+            // A newtype payload missing from `name` may have its fields nested under it.
+            let missing = if self.newtype {
+                let nested = format!("{name}.");
+                quote! {{
+                    let nested: Vec<(redis::Value, redis::Value)> = fields_map
+                        .iter()
+                        .filter_map(|(key, value)| {
+                            let name = key.strip_prefix(#nested).filter(|name| !name.is_empty())?;
+                            Some((
+                                redis::Value::BulkString(name.as_bytes().to_vec()),
+                                (*value).clone(),
+                            ))
+                        })
+                        .collect();
+                    if nested.is_empty() {
+                        #missing
+                    } else {
+                        redis::FromRedisValue::from_redis_value(&redis::Value::Map(nested))
+                            .map_err(|e| redis::RedisError::from((
+                                redis::ErrorKind::TypeError,
+                                "Failed to parse field",
+                                format!("Field '{}': {}", #name, e),
+                            )))?
+                    }
+                }}
+            } else {
+                missing
+            };
+            field_inits.push(quote! {
+                #member: match fields_map.get(#name) {
+                    Some(value) => redis::FromRedisValue::from_redis_value(value)
+                        .map_err(|e| redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Failed to parse field",
+                            format!("Field '{}': {}", #name, e),
+                        )))?,
+                    None => #missing,
+                },
+            });
+        }
+
+        // An untagged unit variant only matches an empty (or missing) hash.
+        let guard = match (tagging, self.fields.is_empty()) {
+            (Tagging::Untagged, true) => {
+                uses_fields_map = true;
+                quote! {
+                    if !fields_map.is_empty() {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Unexpected fields for unit variant",
+                        )));
+                    }
+                }
+            }
+            _ => quote!(),
+        };
+        reads.fields_map |= uses_fields_map;
+
+        // Untagged decoders share one attempt at reading a hash, which only the
+        // variants stored as fields need to succeed.
+        let fields_map = match tagging {
+            Tagging::Untagged if uses_fields_map => quote! {
+                let fields_map = match &fields_map {
+                    Ok(fields_map) => fields_map,
+                    Err(_) => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Expected Array or Map for enum",
+                        )));
+                    }
+                };
+            },
+            _ => quote!(),
+        };
+
+        quote! {
+            (|| -> redis::RedisResult<Self> {
+                #fields_map
+                #guard
+                Ok(Self::#variant_ident { #(#field_inits)* })
+            })()
+        }
+    }
+
+    /// Hash field holding `field`: `prefix.name`, just `prefix` for a newtype, or just
+    /// `name` when the payload is not nested.
+    fn field_key(&self, field: &VariantField, prefix: Option<&str>) -> String {
+        match prefix {
+            Some(prefix) if self.newtype => prefix.to_string(),
+            Some(prefix) => format!("{prefix}.{}", field.name),
+            None => field.name.clone(),
+        }
+    }
+}
+
+fn derive_to_redis_hash_enum<'a>(
+    variants: &[Variant<'a>],
+    type_ident: &Ident,
+    generics: Generics,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let tagging = Tagging::new(attrs);
+    tagging.check_other(variants)?;
+    tagging.check_newtypes(variants)?;
+
+    let mut field_types = Vec::new();
+    let mut write_arms = Vec::new();
+    let mut count_arms = Vec::new();
+
+    for variant in variants {
+        let variant_ident = variant.ident;
+        let prefix = tagging.prefix(&variant.name);
+        let mut writes = Vec::new();
+        let mut counts = Vec::new();
+
+        if let Some(tag) = tagging.tag() {
+            let variant_name = &variant.name;
+            writes.push(quote! {
+                out.write_arg(#tag.as_bytes());
+                out.write_arg(#variant_name.as_bytes());
+            });
+            counts.push(quote!(count += 2;));
+        }
+
+        let written: Vec<_> = variant
+            .fields
+            .iter()
+            .filter(|field| !field.attrs.skips_serializing())
+            .collect();
+
+        if variant.fields.is_empty() {
+            // A unit variant has nothing to store but the name it is keyed by.
+            if let Tagging::External = tagging {
+                let variant_name = &variant.name;
+                writes.push(quote! {
+                    out.write_arg(#variant_name.as_bytes());
+                    out.write_arg(b"");
+                });
+                counts.push(quote!(count += 2;));
+            }
+        } else if variant.newtype && prefix.is_none() {
+            if let Some(field) = written.first() {
+                let binding = &field.binding;
+                writes.push(quote!(#binding.write_redis_args(out);));
+                counts.push(quote!(count += #binding.num_of_args();));
+            }
+        } else {
+            // The variant name is only found in the keys of its payload, so a payload
+            // that writes nothing (all `None`, say) is marked by an empty `name.` field.
+            let marked = matches!(tagging, Tagging::External);
+            let note_payload = marked.then(|| quote!(has_payload = true;));
+            if marked && !written.is_empty() {
+                writes.push(quote!(let mut has_payload = false;));
+                counts.push(quote!(let mut has_payload = false;));
+            }
+
+            for field in &written {
+                let binding = &field.binding;
+                let name = variant.field_key(field, prefix);
+                let is_written = match &field.attrs.skip_serializing_if {
+                    Some(predicate) => quote!(!#predicate(#binding) && #binding.num_of_args() > 0),
+                    None => quote!(#binding.num_of_args() > 0),
+                };
+                if !variant.newtype {
+                    writes.push(quote! {
+                        if #is_written {
+                            out.write_arg(#name.as_bytes());
+                            #binding.write_redis_args(out);
+                            #note_payload
+                        }
+                    });
+                    counts.push(quote! {
+                        if #is_written {
+                            count += 1 + #binding.num_of_args();
+                            #note_payload
+                        }
+                    });
+                    continue;
+                }
+
+                // A newtype payload of one value is stored under `name`; one written as
+                // field/value pairs, such as a struct, has its fields nested under it.
+                let nested = format!("{name}.");
+                writes.push(quote! {
+                    if #is_written {
+                        let args = redis::ToRedisArgs::to_redis_args(#binding);
+                        if let [arg] = args.as_slice() {
+                            out.write_arg(#name.as_bytes());
+                            out.write_arg(arg);
+                        } else {
+                            for (index, arg) in args.iter().enumerate() {
+                                if index % 2 == 0 {
+                                    out.write_arg(&[#nested.as_bytes(), arg.as_slice()].concat());
+                                } else {
+                                    out.write_arg(arg);
+                                }
+                            }
+                        }
+                        #note_payload
+                    }
+                });
+                counts.push(quote! {
+                    if #is_written {
+                        count += match #binding.num_of_args() {
+                            1 => 2,
+                            args => args,
+                        };
+                        #note_payload
+                    }
+                });
+            }
+
+            if marked {
+                let marker = format!("{}.", variant.name);
+                let (write_marker, count_marker) = (
+                    quote! {
+                        out.write_arg(#marker.as_bytes());
+                        out.write_arg(b"");
+                    },
+                    quote!(count += 2;),
+                );
+                if written.is_empty() {
+                    writes.push(write_marker);
+                    counts.push(count_marker);
+                } else {
+                    writes.push(quote!(if !has_payload { #write_marker }));
+                    counts.push(quote!(if !has_payload { #count_marker }));
+                }
+            }
+        }
+
+        field_types.extend(written.iter().map(|field| field.ty));
+        let members = written.iter().map(|field| &field.member);
+        let bindings: Vec<_> = written.iter().map(|field| &field.binding).collect();
+
+        write_arms.push(quote! {
+            Self::#variant_ident { #(#members: #bindings,)* .. } => {
+                #(#writes)*
+            }
+        });
+        let members = written.iter().map(|field| &field.member);
+        count_arms.push(quote! {
+            Self::#variant_ident { #(#members: #bindings,)* .. } => {
+                #(#counts)*
+            }
+        });
+    }
+
+    let generics = util::add_trait_bounds(
+        generics,
+        &field_types,
+        quote!(redis::ToRedisArgs),
+        attrs.bound.as_ref(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
+            fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
+                // Write the variant as hash field/value pairs
+                match self {
+                    #(#write_arms)*
+                }
+            }
+
+            fn num_of_args(&self) -> usize {
+                let mut count = 0;
+                match self {
+                    #(#count_arms)*
+                }
+                count
+            }
+        }
+    })
+}
+
+fn derive_from_redis_hash_enum<'a>(
+    variants: &[Variant<'a>],
+    type_ident: &Ident,
+    generics: Generics,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let tagging = Tagging::new(attrs);
+    tagging.check_other(variants)?;
+    tagging.check_newtypes(variants)?;
+
+    let mut reads = DecodeBounds::default();
+    let decoders: Vec<_> = variants
+        .iter()
+        .map(|variant| {
+            // An externally tagged variant can be found under any of its names.
+            let prefixes: Vec<_> = match tagging {
+                Tagging::External => variant.names().map(|name| Some(name.as_str())).collect(),
+                _ => vec![tagging.prefix(&variant.name)],
+            };
+            prefixes
+                .into_iter()
+                .map(|prefix| variant.decoder(prefix, &tagging, &mut reads))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let variant_list = variants
+        .iter()
+        .flat_map(Variant::names)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let kind = util::value_kind(quote!(v));
+    let read_fields = quote! {
+        fn read_fields(
+            v: &redis::Value,
+        ) -> redis::RedisResult<std::collections::HashMap<String, &redis::Value>> {
+            let mut fields_map = std::collections::HashMap::new();
+            match v {
+                redis::Value::Array(items) if items.len() % 2 == 0 => {
+                    for chunk in items.chunks(2) {
+                        let key: String = redis::FromRedisValue::from_redis_value(&chunk[0])?;
+                        fields_map.insert(key, &chunk[1]);
+                    }
+                }
+                redis::Value::Map(map) => {
+                    for (key, value) in map {
+                        let key: String = redis::FromRedisValue::from_redis_value(key)?;
+                        fields_map.insert(key, value);
+                    }
+                }
+                redis::Value::Nil => {
+                    return Err(redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Cannot deserialize enum from nil value",
+                        format!("Expected hash for {}, got nil", stringify!(#type_ident)),
+                    )));
+                }
+                _ => {
+                    return Err(redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Expected Array or Map for enum",
+                        format!("Expected hash for {}, got {}", stringify!(#type_ident), #kind),
+                    )));
+                }
+            }
+            Ok(fields_map)
+        }
+    };
+
+    let body = match &tagging {
+        Tagging::External => {
+            let checks = variants
+                .iter()
+                .zip(&decoders)
+                .flat_map(|(variant, decoders)| {
+                    variant.names().zip(decoders).map(|(name, decoder)| {
+                    let nested = format!("{name}.");
+                    quote! {
+                        if fields_map.keys().any(|key| key == #name || key.starts_with(#nested)) {
+                            return #decoder;
+                        }
+                    }
+                })
+                });
+            quote! {
+                let fields_map = read_fields(v)?;
+                #(#checks)*
+                Err(redis::RedisError::from((
+                    redis::ErrorKind::TypeError,
+                    "Unknown enum variant",
+                    format!(
+                        "No variant of {} found in hash. Valid variants: [{}]",
+                        stringify!(#type_ident),
+                        #variant_list
+                    ),
+                )))
+            }
+        }
+        Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => {
+            let arms = variants
+                .iter()
+                .zip(&decoders)
+                .map(|(variant, decoders)| (variant.names().collect(), decoders[0].clone()))
+                .collect();
+            let fallback = match variants.iter().position(|variant| variant.other) {
+                Some(index) => Fallback::Wildcard(decoders[index][0].clone()),
+                None => Fallback::Named(quote! {
+                    Err(redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Unknown enum variant",
+                        format!(
+                            "Unknown variant '{}' for {}. Valid variants: [{}]",
+                            unknown,
+                            stringify!(#type_ident),
+                            #variant_list
+                        ),
+                    )))
+                }),
+            };
+            let match_name = match_name(quote!(variant.as_str()), arms, fallback, attrs);
+            quote! {
+                let fields_map = read_fields(v)?;
+                let variant: String = match fields_map.get(#tag) {
+                    Some(value) => redis::FromRedisValue::from_redis_value(value)?,
+                    None => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Missing required field",
+                            #tag.to_string(),
+                        )))
+                    }
+                };
+                #match_name
+            }
+        }
+        Tagging::Untagged => {
+            // A missing key reads as nil, which holds no fields. Any other value that
+            // is not a hash only matches variants decoded from the value itself.
+            let fields_map = reads.fields_map.then(|| {
+                quote! {
+                    let fields_map = match v {
+                        redis::Value::Nil => Ok(std::collections::HashMap::new()),
+                        _ => read_fields(v),
+                    };
+                }
+            });
+            let decoders = decoders.iter().map(|decoders| &decoders[0]);
+            quote! {
+                #fields_map
+                #(
+                    if let Ok(value) = #decoders {
+                        return Ok(value);
+                    }
+                )*
+                Err(redis::RedisError::from((
+                    redis::ErrorKind::TypeError,
+                    "No matching enum variant",
+                    format!(
+                        "Data did not match any variant of untagged enum {}",
+                        stringify!(#type_ident)
+                    ),
+                )))
+            }
+        }
+    };
+
+    let mut generics = util::add_trait_bounds(
+        generics,
+        &reads.field_types,
+        quote!(redis::FromRedisValue),
+        attrs.bound.as_ref(),
+    );
+    if attrs.bound.is_none() {
+        generics = util::add_trait_bounds(generics, &reads.default_types, quote!(Default), None);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                #read_fields
+
+                #body
+            }
+        }
+    })
+}
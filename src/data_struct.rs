@@ -1,21 +1,40 @@
-use crate::util::{self, ParsedAttributeMap};
-use quote::quote;
-use syn::{DataStruct, Fields, Ident};
+use crate::util::{
+    self, DefaultValue, ExpireAt, FieldAttributes, KeySegment, KeyTtl, ParsedAttributeMap,
+};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use syn::{
+    parse_quote, DataStruct, Field, Fields, FieldsUnnamed, Generics, Ident, LitStr, Type,
+    Visibility, WherePredicate,
+};
 
 pub fn derive_to_redis_struct(
     data_struct: DataStruct,
     type_ident: Ident,
+    vis: Visibility,
+    generics: Generics,
     attrs: ParsedAttributeMap,
-) -> proc_macro::TokenStream {
+) -> syn::Result<TokenStream> {
     match &data_struct.fields {
         Fields::Named(fields_named) => {
             let mut regular_fields = Vec::new();
+            let mut flattened_fields = Vec::new();
+            let mut expiring_fields = BTreeMap::new();
+            let mut expire_at_commands = Vec::new();
+            let mut encoded_bindings = Vec::new();
+            let mut expiry_bindings = Vec::new();
 
-            for field in &fields_named.named {
+            let fields = fields_named
+                .named
+                .iter()
+                .map(|field| Ok((field, util::parse_field_attributes(&field.attrs)?)))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            for (field, field_attrs) in &fields {
                 let field_ident = field.ident.as_ref().expect("Named field should have ident");
-                let field_attrs = util::parse_field_attributes(&field.attrs);
 
-                if field_attrs.skip {
+                if field_attrs.skips_serializing() {
                     continue;
                 }
 
@@ -25,42 +44,357 @@ pub fn derive_to_redis_struct(
                     field_attrs.rename.as_ref(),
                 );
 
-                regular_fields.push((field_ident, field_name.clone()));
+                // A `serialize_with` function is called once per generated method, into
+                // a local that the arg count and the write then share.
+                let (encoded_attrs, value) = match &field_attrs.serialize_with {
+                    Some(path) => {
+                        let encoded = format_ident!("__encoded_{}", field_ident);
+                        let binding = quote!(let #encoded = #path(&self.#field_ident););
+                        if field_attrs.expire.is_some() || field_attrs.expire_at.is_some() {
+                            expiry_bindings.push(binding.clone());
+                        }
+                        encoded_bindings.push(binding);
+                        let encoded_attrs = FieldAttributes {
+                            serialize_with: None,
+                            ..field_attrs.clone()
+                        };
+                        (encoded_attrs, quote!(&#encoded))
+                    }
+                    None => (field_attrs.clone(), quote!(&self.#field_ident)),
+                };
+
+                // A field is only written when it contributes at least one arg, so
+                // `None` never leaves a dangling field name in the HSET arguments.
+                let has_value = has_value(&encoded_attrs, &field.ty, value.clone());
+                let is_written = match &field_attrs.skip_serializing_if {
+                    Some(predicate) => quote! {
+                        !#predicate(&self.#field_ident) && #has_value
+                    },
+                    None => has_value,
+                };
+
+                if let Some(seconds) = field_attrs.expire {
+                    expiring_fields
+                        .entry(seconds)
+                        .or_insert_with(Vec::new)
+                        .push((field_name.clone(), is_written.clone()));
+                }
+
+                if let Some(expire_at) = &field_attrs.expire_at {
+                    let source_field = expire_at_source(expire_at, &fields)?;
+                    let source = &expire_at.field;
+                    let command = expire_at_command(
+                        expire_at,
+                        &field_name,
+                        source_field,
+                        quote!(&self.#source),
+                    );
+                    expire_at_commands.push(quote! {
+                        if #is_written {
+                            #command
+                        }
+                    });
+                }
+
+                if field_attrs.flatten {
+                    let prefix = field_attrs.prefix.clone().unwrap_or_default();
+                    flattened_fields.push((field_ident, prefix, &field.ty, is_written));
+                    continue;
+                }
+
+                let field_value =
+                    encoded_value(&encoded_attrs, &field.ty, &field_name, value.clone());
+                let field_args = value_args(&encoded_attrs, value);
+                regular_fields.push((
+                    field,
+                    field_attrs,
+                    field_name,
+                    is_written,
+                    field_value,
+                    field_args,
+                ));
+            }
+
+            // One HEXPIRE per distinct expiry, covering the fields that were written.
+            let expire_commands = expiring_fields.iter().map(|(seconds, fields)| {
+                let (names, conditions): (Vec<_>, Vec<_>) = fields.iter().cloned().unzip();
+                quote! {
+                    let mut fields: Vec<&'static str> = Vec::new();
+                    #(
+                        if #conditions {
+                            fields.push(#names);
+                        }
+                    )*
+                    if !fields.is_empty() {
+                        pipe.cmd("HEXPIRE")
+                            .arg(&key)
+                            .arg(#seconds)
+                            .arg("FIELDS")
+                            .arg(fields.len())
+                            .arg(&fields)
+                            .ignore();
+                    }
+                }
+            });
+
+            let (key_items, key_method) = match &attrs.key {
+                Some(template) => key_template(template, &type_ident, &vis, &fields)?,
+                None => (quote!(), quote!()),
+            };
+
+            let cluster_key_methods = match &attrs.cluster_key {
+                Some(cluster_key) => {
+                    cluster_key_methods(cluster_key, &type_ident, &attrs, &fields)?
+                }
+                None => quote!(),
+            };
+
+            let repository_methods = if attrs.repository {
+                repository_methods(&type_ident, &attrs, &fields)?
+            } else {
+                quote!()
+            };
+
+            let key_ttl = match attrs.key_ttl {
+                Some(KeyTtl { value, millis }) => {
+                    let unit = if millis {
+                        quote!(from_millis)
+                    } else {
+                        quote!(from_secs)
+                    };
+                    quote!(Some(std::time::Duration::#unit(#value)))
+                }
+                None => quote!(None),
+            };
+            let key_ttl_mode = attrs.key_ttl_mode.as_ref().map(|mode| quote!(.arg(#mode)));
+
+            let cache_methods = if attrs.cache {
+                cache_methods(&type_ident, &generics, &attrs)?
+            } else {
+                quote!()
+            };
+
+            let field_names: Vec<_> = regular_fields.iter().map(|(_, _, name, ..)| name).collect();
+            let is_written: Vec<_> = regular_fields
+                .iter()
+                .map(|(_, _, _, written, ..)| written)
+                .collect();
+            let field_values: Vec<_> = regular_fields.iter().map(|(.., value, _)| value).collect();
+            let field_args: Vec<_> = regular_fields.iter().map(|(.., args)| args).collect();
+
+            let flattened_idents: Vec<_> =
+                flattened_fields.iter().map(|(ident, ..)| *ident).collect();
+            let flattened_written: Vec<_> = flattened_fields
+                .iter()
+                .map(|(.., written)| written)
+                .collect();
+            let flattened_writes = flattened_fields.iter().map(|(ident, prefix, ..)| {
+                if prefix.is_empty() {
+                    return quote! { (&self.#ident).write_redis_args(out); };
+                }
+                // Every other arg of the nested value is a field name to prefix.
+                quote! {
+                    let args = redis::ToRedisArgs::to_redis_args(&self.#ident);
+                    for (index, arg) in args.iter().enumerate() {
+                        if index % 2 == 0 {
+                            out.write_arg(&[#prefix.as_bytes(), arg.as_slice()].concat());
+                        } else {
+                            out.write_arg(arg);
+                        }
+                    }
+                }
+            });
+
+            let (json_fields, redis_fields): (Vec<_>, Vec<_>) = regular_fields
+                .iter()
+                .partition(|(_, field_attrs, ..)| field_attrs.json);
+            let field_types: Vec<&Type> = redis_fields
+                .iter()
+                .filter(|(_, field_attrs, ..)| {
+                    !field_attrs.as_string && field_attrs.serialize_with.is_none()
+                })
+                .map(|(field, ..)| &field.ty)
+                .chain(flattened_fields.iter().map(|(_, _, ty, _)| *ty))
+                .collect();
+            let json_types: Vec<&Type> = json_fields.iter().map(|(field, ..)| &field.ty).collect();
+            let string_types: Vec<&Type> = redis_fields
+                .iter()
+                .filter(|(_, field_attrs, ..)| field_attrs.as_string)
+                .map(|(field, ..)| util::option_inner(&field.ty).unwrap_or(&field.ty))
+                .collect();
+
+            let mut generics = util::add_trait_bounds(
+                generics,
+                &field_types,
+                quote!(redis::ToRedisArgs),
+                attrs.bound.as_ref(),
+            );
+            if attrs.bound.is_none() {
+                generics =
+                    util::add_trait_bounds(generics, &json_types, quote!(serde::Serialize), None);
+                generics = util::string_bounds(generics, &string_types, false);
             }
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+            // Inherent helpers are only generated when requested or needed by another
+            // attribute, so they don't clash with methods the type defines itself.
+            let omitted_fields_method = if attrs.omitted_fields || attrs.repository {
+                quote! {
+                    /// Hash fields left out of the `ToRedisArgs` output because they are
+                    /// `None`, empty or matched `skip_serializing_if`. Pass them to `HDEL`
+                    /// to clear values stored by an earlier write.
+                    #[allow(dead_code)]
+                    pub fn omitted_fields(&self) -> Vec<&'static str> {
+                        use redis::ToRedisArgs;
+
+                        #(#encoded_bindings)*
+                        let mut omitted = Vec::new();
+                        #(
+                            if !(#is_written) {
+                                omitted.push(#field_names);
+                            }
+                        )*
+                        omitted
+                    }
+                }
+            } else {
+                quote!()
+            };
+            let expiry_methods = if attrs.repository
+                || attrs.key_ttl.is_some()
+                || !expiring_fields.is_empty()
+                || !expire_at_commands.is_empty()
+            {
+                quote! {
+                    /// Expiry of the whole key applied by `write_with_expiry` and `save`,
+                    /// from `#[redis(key_ttl = "...")]`.
+                    #[allow(dead_code)]
+                    pub const KEY_TTL: Option<std::time::Duration> = #key_ttl;
+
+                    /// Queue an `HSET` of this value at `key` on `pipe`, followed by one
+                    /// `HEXPIRE` (Redis 7.4+) per distinct `#[redis(expire = "...")]` value,
+                    /// an `HEXPIREAT`/`HPEXPIREAT` per `expire_at`/`expire_at_ms` field and
+                    /// an `EXPIRE` of the key for `KEY_TTL`. The expiry replies are ignored.
+                    #[allow(dead_code)]
+                    pub fn write_with_expiry<'pipe>(
+                        &self,
+                        key: impl redis::ToRedisArgs,
+                        pipe: &'pipe mut redis::Pipeline,
+                    ) -> &'pipe mut redis::Pipeline {
+                        self.write_with_ttl(key, Self::KEY_TTL, pipe)
+                    }
 
-            let (field_idents, field_names): (Vec<_>, Vec<_>) =
-                regular_fields.into_iter().unzip();
+                    /// Like `write_with_expiry`, but expires the key after `ttl` instead
+                    /// of `KEY_TTL` (`EXPIRE`, or `PEXPIRE` for sub-second durations).
+                    /// `None` or a zero duration leaves the key's expiry untouched.
+                    #[allow(dead_code)]
+                    pub fn write_with_ttl<'pipe>(
+                        &self,
+                        key: impl redis::ToRedisArgs,
+                        ttl: Option<std::time::Duration>,
+                        pipe: &'pipe mut redis::Pipeline,
+                    ) -> &'pipe mut redis::Pipeline {
+                        use redis::ToRedisArgs;
+
+                        let args = self.to_redis_args();
+                        if !args.is_empty() {
+                            pipe.cmd("HSET").arg(&key).arg(args);
+                        }
+                        #(#expiry_bindings)*
+                        #({ #expire_commands })*
+                        #(#expire_at_commands)*
+                        // A zero expiry would delete the key, so it is ignored like `None`,
+                        // and sub-millisecond durations are rounded up to one millisecond.
+                        if let Some(ttl) = ttl.filter(|ttl| !ttl.is_zero()) {
+                            if ttl.subsec_nanos() == 0 {
+                                pipe.cmd("EXPIRE").arg(&key).arg(ttl.as_secs());
+                            } else {
+                                let millis = (ttl.as_millis() as u64).max(1);
+                                pipe.cmd("PEXPIRE").arg(&key).arg(millis);
+                            }
+                            pipe #key_ttl_mode.ignore();
+                        }
+                        pipe
+                    }
+                }
+            } else {
+                quote!()
+            };
 
             // Generate the basic ToRedisArgs implementation
             let to_redis_impl = quote! {
-                impl redis::ToRedisArgs for #type_ident {
+                impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
                     fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
+                        #(#encoded_bindings)*
                         // Write each field as key-value pairs for hash storage
                         #(
-                            out.write_arg(#field_names.as_bytes());
-                            (&self.#field_idents).write_redis_args(out);
+                            if #is_written {
+                                out.write_arg(#field_names.as_bytes());
+                                (#field_values).write_redis_args(out);
+                            }
+                        )*
+                        // Flattened fields contribute their own field/value pairs
+                        #(
+                            if #flattened_written {
+                                #flattened_writes
+                            }
                         )*
                     }
 
                     fn num_of_args(&self) -> usize {
+                        #(#encoded_bindings)*
                         let mut count = 0;
                         #(
-                            count += 1; // field name
-                            count += (&self.#field_idents).num_of_args(); // field value args
+                            if #is_written {
+                                count += 1; // field name
+                                count += #field_args; // field value args
+                            }
+                        )*
+                        // Counted from the args themselves: maps report one per entry
+                        #(
+                            if #flattened_written {
+                                count += redis::ToRedisArgs::to_redis_args(&self.#flattened_idents).len();
+                            }
                         )*
                         count
                     }
                 }
+
+                impl #impl_generics #type_ident #ty_generics #where_clause {
+                    #omitted_fields_method
+
+                    #expiry_methods
+
+                    #cluster_key_methods
+
+                    #key_method
+
+                    #repository_methods
+
+                    #cache_methods
+                }
+
+                #key_items
             };
 
-            to_redis_impl.into()
+            Ok(to_redis_impl)
         }
         Fields::Unnamed(fields_unnamed) => {
+            check_tuple_fields(fields_unnamed)?;
             let field_count = fields_unnamed.unnamed.len();
-            let indices: Vec<usize> = (0..field_count).collect();
+            let indices: Vec<syn::Index> = (0..field_count).map(syn::Index::from).collect();
+            let field_types: Vec<&Type> = fields_unnamed.unnamed.iter().map(|f| &f.ty).collect();
+
+            let generics = util::add_trait_bounds(
+                generics,
+                &field_types,
+                quote!(redis::ToRedisArgs),
+                attrs.bound.as_ref(),
+            );
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
             let to_redis_impl = quote! {
-                impl redis::ToRedisArgs for #type_ident {
+                impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
                     fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
                         // Write tuple struct fields as an array
                         #(
@@ -78,11 +412,13 @@ pub fn derive_to_redis_struct(
                 }
             };
 
-            to_redis_impl.into()
+            Ok(to_redis_impl)
         }
         Fields::Unit => {
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
             let to_redis_impl = quote! {
-                impl redis::ToRedisArgs for #type_ident {
+                impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
                     fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, _out: &mut W) {
                         // Unit structs don't write any args
                     }
@@ -93,7 +429,7 @@ pub fn derive_to_redis_struct(
                 }
             };
 
-            to_redis_impl.into()
+            Ok(to_redis_impl)
         }
     }
 }
@@ -101,17 +437,68 @@ pub fn derive_to_redis_struct(
 pub fn derive_from_redis_struct(
     data_struct: DataStruct,
     type_ident: Ident,
+    generics: Generics,
     attrs: ParsedAttributeMap,
-) -> proc_macro::TokenStream {
+) -> syn::Result<TokenStream> {
     match &data_struct.fields {
         Fields::Named(fields_named) => {
-            let mut regular_fields = Vec::new();
+            let mut field_inits = Vec::new();
+            let mut field_types = Vec::new();
+            let mut json_types = Vec::new();
+            let mut string_types = Vec::new();
+            let mut default_types = Vec::new();
 
-            for field in &fields_named.named {
+            let fields = fields_named
+                .named
+                .iter()
+                .map(|field| Ok((field, util::parse_field_attributes(&field.attrs)?)))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            // Keys an unprefixed flattened field leaves to the rest of the struct.
+            let mut own_names = Vec::new();
+            let mut prefixes = Vec::new();
+            for (field, field_attrs) in &fields {
+                if field_attrs.skips_deserializing() {
+                    continue;
+                }
+                if !field_attrs.flatten {
+                    let field_ident = field.ident.as_ref().expect("Named field should have ident");
+                    own_names.push(util::transform_field_name(
+                        &field_ident.to_string(),
+                        attrs.rename_all.as_ref(),
+                        field_attrs.rename.as_ref(),
+                    ));
+                } else if let Some(prefix) = field_attrs.prefix.as_deref() {
+                    if !prefix.is_empty() {
+                        prefixes.push(prefix);
+                    }
+                }
+            }
+
+            for (field, field_attrs) in &fields {
                 let field_ident = field.ident.as_ref().expect("Named field should have ident");
-                let field_attrs = util::parse_field_attributes(&field.attrs);
+                let skipped = field_attrs.skips_deserializing();
+
+                // Value for a field that is skipped or absent from the reply:
+                // field default, then container default, then `Default`/`None`.
+                let fallback = match (&field_attrs.default, &attrs.default) {
+                    (Some(default), _) => {
+                        if let DefaultValue::Trait = default {
+                            default_types.push(&field.ty);
+                        }
+                        Some(default.to_expr())
+                    }
+                    (None, Some(_)) => Some(quote!(__default.#field_ident)),
+                    (None, None) if skipped => {
+                        default_types.push(&field.ty);
+                        Some(DefaultValue::Trait.to_expr())
+                    }
+                    (None, None) if util::is_option(&field.ty) => Some(quote!(None)),
+                    (None, None) => None,
+                };
 
-                if field_attrs.skip {
+                if skipped {
+                    field_inits.push(quote! { #field_ident: #fallback, });
                     continue;
                 }
 
@@ -121,101 +508,143 @@ pub fn derive_from_redis_struct(
                     field_attrs.rename.as_ref(),
                 );
 
-                regular_fields.push((field_ident, field_name));
+                if field_attrs.flatten {
+                    field_types.push(&field.ty);
+                    field_inits.push(flattened_field_init(
+                        field_ident,
+                        &field_name,
+                        field_attrs.prefix.as_deref().unwrap_or_default(),
+                        fallback,
+                        &own_names,
+                        &prefixes,
+                    ));
+                    continue;
+                }
+
+                let missing = match fallback {
+                    Some(fallback) => fallback,
+                    None => quote! {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Missing required field",
+                            #field_name.to_string(),
+                        )))
+                    },
+                };
+
+                if field_attrs.json {
+                    json_types.push(&field.ty);
+                } else if field_attrs.as_string {
+                    string_types.push(util::option_inner(&field.ty).unwrap_or(&field.ty));
+                } else if field_attrs.deserialize_with.is_none() {
+                    field_types.push(&field.ty);
+                }
+                let decoded = decoded_value(field_attrs, &field.ty, quote!(value));
+                field_inits.push(quote! {
+                    #field_ident: match fields_map.get(#field_name) {
+                        Some(value) => #decoded
+                            .map_err(|e| redis::RedisError::from((
+                                redis::ErrorKind::TypeError,
+                                "Failed to parse field",
+                                format!("Field '{}': {}", #field_name, e),
+                            )))?,
+                        None => #missing,
+                    },
+                });
             }
 
-            let (field_idents, field_names): (Vec<_>, Vec<_>) =
-                regular_fields.into_iter().unzip();
+            let container_default = attrs.default.as_ref().map(|default| {
+                let default = default.to_expr();
+                quote! { let __default: Self = #default; }
+            });
+
+            let mut generics = util::add_trait_bounds(
+                generics,
+                &field_types,
+                quote!(redis::FromRedisValue),
+                attrs.bound.as_ref(),
+            );
+            if attrs.bound.is_none() {
+                generics = util::add_trait_bounds(generics, &default_types, quote!(Default), None);
+                generics = util::add_trait_bounds(
+                    generics,
+                    &json_types,
+                    quote!(serde::de::DeserializeOwned),
+                    None,
+                );
+                generics = util::string_bounds(generics, &string_types, true);
+                // A container `default` fills missing fields from the type's own impl.
+                if let Some(DefaultValue::Trait) = attrs.default {
+                    let (_, ty_generics, _) = generics.split_for_impl();
+                    let predicate: WherePredicate = parse_quote!(#type_ident #ty_generics: Default);
+                    generics.make_where_clause().predicates.push(predicate);
+                }
+            }
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
             let from_redis_impl = quote! {
-                impl redis::FromRedisValue for #type_ident {
+                impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
                     fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                        let mut fields_map = std::collections::HashMap::new();
+
                         match v {
                             redis::Value::Array(items) if items.len() % 2 == 0 => {
-                                let mut fields_map = std::collections::HashMap::new();
-                                
                                 // Parse key-value pairs from array
                                 for chunk in items.chunks(2) {
                                     let key: String = redis::FromRedisValue::from_redis_value(&chunk[0])?;
                                     fields_map.insert(key, &chunk[1]);
                                 }
-
-                                Ok(Self {
-                                    #(
-                                        #field_idents: {
-                                            match fields_map.get(#field_names) {
-                                                Some(value) => redis::FromRedisValue::from_redis_value(value)
-                                                    .map_err(|e| redis::RedisError::from((
-                                                        redis::ErrorKind::TypeError,
-                                                        "Failed to parse field",
-                                                        format!("Field '{}': {}", #field_names, e),
-                                                    )))?,
-                                                None => return Err(redis::RedisError::from((
-                                                    redis::ErrorKind::TypeError,
-                                                    "Missing required field",
-                                                    #field_names.to_string(),
-                                                ))),
-                                            }
-                                        },
-                                    )*
-                                })
                             }
                             redis::Value::Map(map) => {
                                 // Handle Redis hash/map type (RESP3)
-                                let mut fields_map = std::collections::HashMap::new();
-                                
                                 for (key, value) in map {
                                     let key_str: String = redis::FromRedisValue::from_redis_value(key)?;
                                     fields_map.insert(key_str, value);
                                 }
-
-                                Ok(Self {
-                                    #(
-                                        #field_idents: {
-                                            match fields_map.get(#field_names) {
-                                                Some(value) => redis::FromRedisValue::from_redis_value(value)
-                                                    .map_err(|e| redis::RedisError::from((
-                                                        redis::ErrorKind::TypeError,
-                                                        "Failed to parse field",
-                                                        format!("Field '{}': {}", #field_names, e),
-                                                    )))?,
-                                                None => return Err(redis::RedisError::from((
-                                                    redis::ErrorKind::TypeError,
-                                                    "Missing required field",
-                                                    #field_names.to_string(),
-                                                ))),
-                                            }
-                                        },
-                                    )*
-                                })
                             }
                             redis::Value::Nil => {
-                                Err(redis::RedisError::from((
+                                return Err(redis::RedisError::from((
                                     redis::ErrorKind::TypeError,
                                     "Cannot deserialize struct from nil value",
-                                )))
+                                )));
                             }
                             _ => {
-                                Err(redis::RedisError::from((
+                                return Err(redis::RedisError::from((
                                     redis::ErrorKind::TypeError,
                                     "Expected Array or Map for struct",
-                                )))
+                                )));
                             }
                         }
+
+                        #container_default
+
+                        Ok(Self {
+                            #(#field_inits)*
+                        })
                     }
                 }
             };
 
-            from_redis_impl.into()
+            Ok(from_redis_impl)
         }
         Fields::Unnamed(fields_unnamed) => {
+            check_tuple_fields(fields_unnamed)?;
             let field_count = fields_unnamed.unnamed.len();
             let indices: Vec<syn::Index> = (0..field_count)
                 .map(syn::Index::from)
                 .collect();
+            let field_types: Vec<&Type> = fields_unnamed.unnamed.iter().map(|f| &f.ty).collect();
+
+            let generics = util::add_trait_bounds(
+                generics,
+                &field_types,
+                quote!(redis::FromRedisValue),
+                attrs.bound.as_ref(),
+            );
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
             let from_redis_impl = quote! {
-                impl redis::FromRedisValue for #type_ident {
+                impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
                     fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
                         match v {
                             redis::Value::Array(items) => {
@@ -255,18 +684,1003 @@ pub fn derive_from_redis_struct(
                 }
             };
 
-            from_redis_impl.into()
+            Ok(from_redis_impl)
         }
         Fields::Unit => {
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
             let from_redis_impl = quote! {
-                impl redis::FromRedisValue for #type_ident {
+                impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
                     fn from_redis_value(_v: &redis::Value) -> redis::RedisResult<Self> {
                         Ok(Self)
                     }
                 }
             };
 
-            from_redis_impl.into()
+            Ok(from_redis_impl)
+        }
+    }
+}
+
+/// Tuple struct fields are written by position, so they take no field attributes.
+fn check_tuple_fields(fields: &FieldsUnnamed) -> syn::Result<()> {
+    for field in &fields.unnamed {
+        util::parse_field_attributes(&field.attrs)?;
+        let mut attrs = field.attrs.iter();
+        if let Some(attr) = attrs.find(|attr| attr.path().is_ident("redis")) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "redis field attributes are not supported on tuple struct fields",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the field value `value` (a `&T` expression) is written at all. A JSON or
+/// `as_string` field always has a value except for `None`.
+fn has_value(field_attrs: &FieldAttributes, ty: &Type, value: TokenStream) -> TokenStream {
+    if field_attrs.json || field_attrs.as_string {
+        if util::is_option(ty) {
+            quote!(Option::is_some(#value))
+        } else {
+            quote!(true)
+        }
+    } else {
+        let args = value_args(field_attrs, value);
+        quote!(#args > 0)
+    }
+}
+
+/// `ToRedisArgs` expression written as the hash field value for `value` (a `&T`
+/// expression).
+fn encoded_value(
+    field_attrs: &FieldAttributes,
+    ty: &Type,
+    field_name: &str,
+    value: TokenStream,
+) -> TokenStream {
+    if field_attrs.json {
+        quote! {
+            serde_json::to_vec(#value).unwrap_or_else(|e| {
+                panic!("Failed to serialize field '{}' as JSON: {}", #field_name, e)
+            })
+        }
+    } else if field_attrs.as_string && util::is_option(ty) {
+        quote!(Option::map(Option::as_ref(#value), ToString::to_string))
+    } else if field_attrs.as_string {
+        quote!(ToString::to_string(#value))
+    } else if let Some(path) = &field_attrs.serialize_with {
+        quote!(#path(#value))
+    } else {
+        value
+    }
+}
+
+/// Number of args `encoded_value` writes for `value`.
+fn value_args(field_attrs: &FieldAttributes, value: TokenStream) -> TokenStream {
+    if field_attrs.json || field_attrs.as_string {
+        quote!(1)
+    } else if let Some(path) = &field_attrs.serialize_with {
+        quote!(redis::ToRedisArgs::num_of_args(&#path(#value)))
+    } else {
+        quote!((#value).num_of_args())
+    }
+}
+
+/// `RedisResult` expression decoding the hash field value `value` (a `&redis::Value`
+/// expression) into the field's type.
+fn decoded_value(field_attrs: &FieldAttributes, ty: &Type, value: TokenStream) -> TokenStream {
+    if field_attrs.json {
+        quote! {
+            <Vec<u8> as redis::FromRedisValue>::from_redis_value(#value).and_then(|raw| {
+                serde_json::from_slice(&raw).map_err(|e| {
+                    redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Invalid JSON",
+                        e.to_string(),
+                    ))
+                })
+            })
+        }
+    } else if field_attrs.as_string {
+        let parsed = if util::is_option(ty) {
+            quote!(text.parse().map(Some))
+        } else {
+            quote!(text.parse())
+        };
+        quote! {
+            <String as redis::FromRedisValue>::from_redis_value(#value).and_then(|text| {
+                #parsed.map_err(|e| {
+                    redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Invalid value",
+                        format!("{:?}: {}", text, e),
+                    ))
+                })
+            })
+        }
+    } else if let Some(path) = &field_attrs.deserialize_with {
+        quote!(#path(#value))
+    } else {
+        quote!(redis::FromRedisValue::from_redis_value(#value))
+    }
+}
+
+/// Initialiser for a `#[redis(flatten)]` field: decode the field type from the entries
+/// of the parent hash under `prefix` (with the prefix removed), or, without a prefix,
+/// from every entry the parent's own fields and prefixed flattened fields don't claim.
+/// If no entry matches, the field's fallback is used when it has one.
+fn flattened_field_init(
+    field_ident: &Ident,
+    field_name: &str,
+    prefix: &str,
+    fallback: Option<TokenStream>,
+    own_names: &[String],
+    prefixes: &[&str],
+) -> TokenStream {
+    let select = if prefix.is_empty() {
+        quote! {
+            let own: &[&str] = &[#(#own_names),*];
+            let prefixes: &[&str] = &[#(#prefixes),*];
+            let claimed = own.contains(&key.as_str())
+                || prefixes.iter().any(|prefix| key.starts_with(prefix));
+            let name = (!claimed).then_some(key.as_str());
+        }
+    } else {
+        quote! { let name = key.strip_prefix(#prefix); }
+    };
+
+    let parse = quote! {
+        redis::FromRedisValue::from_redis_value(&redis::Value::Map(nested))
+            .map_err(|e| redis::RedisError::from((
+                redis::ErrorKind::TypeError,
+                "Failed to parse field",
+                format!("Field '{}': {}", #field_name, e),
+            )))?
+    };
+    let decode = match fallback {
+        Some(fallback) => quote! {
+            if nested.is_empty() {
+                #fallback
+            } else {
+                #parse
+            }
+        },
+        None => parse,
+    };
+
+    quote! {
+        #field_ident: {
+            let nested: Vec<(redis::Value, redis::Value)> = fields_map
+                .iter()
+                .filter_map(|(key, value)| {
+                    #select
+                    Some((
+                        redis::Value::BulkString(name?.as_bytes().to_vec()),
+                        (*value).clone(),
+                    ))
+                })
+                .collect();
+            #decode
+        },
+    }
+}
+
+/// The sibling field named in `expire_at`, which must exist and be written.
+fn expire_at_source<'a>(
+    expire_at: &ExpireAt,
+    fields: &[(&'a Field, FieldAttributes)],
+) -> syn::Result<&'a Field> {
+    let source = &expire_at.field;
+    let (source_field, source_attrs) = fields
+        .iter()
+        .find(|(field, _)| field.ident.as_ref() == Some(source))
+        .ok_or_else(|| syn::Error::new(source.span(), format!("no field named `{source}`")))?;
+
+    if source_attrs.skips_serializing() {
+        return Err(syn::Error::new(
+            source.span(),
+            format!("`expire_at` cannot refer to skipped field `{source}`"),
+        ));
+    }
+    Ok(source_field)
+}
+
+/// `HEXPIREAT` (or `HPEXPIREAT` for `expire_at_ms`) expiring `field_name` at the
+/// Unix timestamp `source` refers to, a reference to a value of the sibling field's type.
+fn expire_at_command(
+    expire_at: &ExpireAt,
+    field_name: &str,
+    source_field: &Field,
+    source: TokenStream,
+) -> TokenStream {
+    let (command, to_timestamp) = if expire_at.millis {
+        ("HPEXPIREAT", quote!(as_millis() as u64))
+    } else {
+        ("HEXPIREAT", quote!(as_secs()))
+    };
+
+    let source_ty = util::option_inner(&source_field.ty).unwrap_or(&source_field.ty);
+    let timestamp = if util::is_system_time(source_ty) {
+        quote! {
+            timestamp
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .#to_timestamp
+        }
+    } else {
+        quote!(timestamp)
+    };
+
+    let command = quote! {
+        pipe.cmd(#command)
+            .arg(&key)
+            .arg(#timestamp)
+            .arg("FIELDS")
+            .arg(1)
+            .arg(#field_name)
+            .ignore();
+    };
+
+    if util::is_option(&source_field.ty) {
+        quote! {
+            if let Some(timestamp) = #source {
+                #command
+            }
+        }
+    } else {
+        quote! {
+            let timestamp = #source;
+            #command
+        }
+    }
+}
+
+/// `redis_key`, `key_for` and `slot` for `#[redis(cluster_key = "field")]`: keys look
+/// like `prefix:{id}`, so every key sharing the hash tag lands on the same cluster slot.
+fn cluster_key_methods(
+    cluster_key: &Ident,
+    type_ident: &Ident,
+    attrs: &ParsedAttributeMap,
+    fields: &[(&Field, FieldAttributes)],
+) -> syn::Result<TokenStream> {
+    if !fields
+        .iter()
+        .any(|(field, _)| field.ident.as_ref() == Some(cluster_key))
+    {
+        return Err(syn::Error::new(
+            cluster_key.span(),
+            format!("no field named `{cluster_key}`"),
+        ));
+    }
+
+    let prefix = match &attrs.key_prefix {
+        Some(prefix) => prefix.value(),
+        None => util::transform_variant_name(&type_ident.to_string(), Some(&"snake_case".into())),
+    };
+    let key_format = if prefix.is_empty() {
+        "{{{}}}".to_string()
+    } else {
+        let prefix = prefix.replace('{', "{{").replace('}', "}}");
+        format!("{prefix}:{{{{{{}}}}}}")
+    };
+
+    Ok(quote! {
+        /// Cluster-aware key for this value, with the `cluster_key` field as hash tag.
+        #[allow(dead_code)]
+        pub fn redis_key(&self) -> String {
+            Self::key_for(&self.#cluster_key)
+        }
+
+        /// Cluster-aware key for the value whose `cluster_key` field equals `id`.
+        #[allow(dead_code)]
+        pub fn key_for(id: impl std::fmt::Display) -> String {
+            format!(#key_format, id)
+        }
+
+        /// Redis Cluster hash slot of `redis_key()` (CRC16 of the hash tag, mod 16384).
+        /// As in Redis, the tag is the text between the first `{` and the next `}`, and
+        /// the whole key is hashed when there is no such text.
+        #[allow(dead_code)]
+        pub fn slot(&self) -> u16 {
+            let key = self.redis_key();
+            let key = key.as_bytes();
+            let tag = key
+                .iter()
+                .position(|&byte| byte == b'{')
+                .and_then(|open| {
+                    let rest = &key[open + 1..];
+                    let close = rest.iter().position(|&byte| byte == b'}')?;
+                    Some(&rest[..close])
+                })
+                .filter(|tag| !tag.is_empty())
+                .unwrap_or(key);
+            let mut crc: u16 = 0;
+            for &byte in tag {
+                crc ^= u16::from(byte) << 8;
+                for _ in 0..8 {
+                    crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+                }
+            }
+            crc % 16384
+        }
+    })
+}
+
+/// The `<Type>Key` newtype and `key()` method for `#[redis(key = "template")]`.
+fn key_template(
+    template: &LitStr,
+    type_ident: &Ident,
+    vis: &Visibility,
+    fields: &[(&Field, FieldAttributes)],
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let segments = util::parse_key_template(&template.value())
+        .map_err(|message| syn::Error::new(template.span(), message))?;
+
+    let mut key_format = String::new();
+    let mut placeholders = Vec::new();
+    for segment in &segments {
+        match segment {
+            KeySegment::Literal(text) => {
+                key_format.push_str(&text.replace('{', "{{").replace('}', "}}"));
+            }
+            KeySegment::Field(name) => {
+                let field_ident = fields
+                    .iter()
+                    .filter_map(|(field, _)| field.ident.as_ref())
+                    .find(|ident| *ident == name)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            template.span(),
+                            format!("key template refers to unknown field `{name}`"),
+                        )
+                    })?;
+                key_format.push_str("{}");
+                placeholders.push(field_ident);
+            }
+        }
+    }
+
+    let mut params = Vec::new();
+    for placeholder in &placeholders {
+        if !params.contains(placeholder) {
+            params.push(*placeholder);
+        }
+    }
+
+    let key_ident = format_ident!("{}Key", type_ident);
+    let key_doc = format!(
+        "Redis key of a [`{type_ident}`], built from `{}`.",
+        template.value()
+    );
+
+    let key_items = quote! {
+        #[doc = #key_doc]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #vis struct #key_ident(pub String);
+
+        impl #key_ident {
+            /// Build the key from the values of the template's fields.
+            #[allow(dead_code)]
+            pub fn new(#(#params: impl std::fmt::Display),*) -> Self {
+                Self(format!(#key_format, #(#placeholders),*))
+            }
+        }
+
+        impl std::fmt::Display for #key_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl redis::ToRedisArgs for #key_ident {
+            fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
+                out.write_arg(self.0.as_bytes());
+            }
+        }
+
+        impl redis::FromRedisValue for #key_ident {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                Ok(Self(redis::FromRedisValue::from_redis_value(v)?))
+            }
+        }
+    };
+
+    let key_method = quote! {
+        /// Redis key this value is stored at.
+        #[allow(dead_code)]
+        pub fn key(&self) -> #key_ident {
+            #key_ident(format!(#key_format, #(self.#placeholders),*))
+        }
+    };
+
+    Ok((key_items, key_method))
+}
+
+/// Persistence helpers for `#[redis(repository)]`, keyed by the `key` template or,
+/// failing that, the `cluster_key` field. With the `async` feature every helper also
+/// gets an `_async` counterpart over `redis::aio::ConnectionLike`.
+fn repository_methods(
+    type_ident: &Ident,
+    attrs: &ParsedAttributeMap,
+    fields: &[(&Field, FieldAttributes)],
+) -> syn::Result<TokenStream> {
+    let own_key = if attrs.key.is_some() {
+        quote!(self.key())
+    } else if attrs.cluster_key.is_some() {
+        quote!(self.redis_key())
+    } else {
+        return Err(syn::Error::new(
+            type_ident.span(),
+            "`repository` requires a `key` template or a `cluster_key` field",
+        ));
+    };
+
+    check_field_helper_names(attrs, fields)?;
+
+    let save_pipeline = quote! {
+        /// The atomic pipeline run by `save_with_ttl`: `HDEL` of the omitted fields, then
+        /// the commands queued by `write_with_ttl`, all in one `MULTI` block.
+        #[allow(dead_code)]
+        pub fn save_pipeline(&self, ttl: Option<std::time::Duration>) -> redis::Pipeline {
+            let key = #own_key;
+            let mut pipe = redis::pipe();
+            pipe.atomic();
+
+            let omitted = self.omitted_fields();
+            if !omitted.is_empty() {
+                pipe.cmd("HDEL").arg(&key).arg(&omitted).ignore();
+            }
+            self.write_with_ttl(&key, ttl, &mut pipe);
+            pipe
+        }
+    };
+    let sync_methods = persistence_methods(false, attrs, fields)?;
+    let async_methods = if cfg!(feature = "async") {
+        persistence_methods(true, attrs, fields)?
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        #save_pipeline
+        #sync_methods
+        #async_methods
+    })
+}
+
+/// Per-field helpers are named after their field, so a field such as `cached` or
+/// `with_ttl` would generate a second `load_cached` or `save_with_ttl`.
+fn check_field_helper_names(
+    attrs: &ParsedAttributeMap,
+    fields: &[(&Field, FieldAttributes)],
+) -> syn::Result<()> {
+    let mut suffixes = vec![""];
+    if cfg!(feature = "async") {
+        suffixes.push("_async");
+    }
+    let mut reserved = Vec::new();
+    for suffix in &suffixes {
+        reserved.push((format!("save_with_ttl{suffix}"), "repository"));
+    }
+    reserved.push(("save_pipeline".to_string(), "repository"));
+    if attrs.cache {
+        for suffix in &suffixes {
+            reserved.push((format!("load_cached{suffix}"), "cache"));
+        }
+    }
+
+    for (field, field_attrs) in fields {
+        if field_attrs.flatten {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().expect("Named field should have ident");
+        let helpers = [
+            ("load", !field_attrs.skips_deserializing()),
+            ("save", !field_attrs.skips_serializing()),
+        ];
+        for (verb, _) in helpers.iter().filter(|(_, generated)| *generated) {
+            for suffix in &suffixes {
+                let name = format!("{verb}_{field_ident}{suffix}");
+                if let Some((_, origin)) = reserved.iter().find(|(taken, _)| *taken == name) {
+                    return Err(syn::Error::new(
+                        field_ident.span(),
+                        format!(
+                            "field `{field_ident}` would generate `{name}`, which `{origin}` already generates; rename the field"
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// What the blocking and `_async` forms of a generated helper differ by.
+struct Flavor {
+    asyncness: TokenStream,
+    dot_await: TokenStream,
+    connection: TokenStream,
+    query: TokenStream,
+    suffix: &'static str,
+}
+
+impl Flavor {
+    fn new(is_async: bool) -> Self {
+        if is_async {
+            Flavor {
+                asyncness: quote!(async),
+                dot_await: quote!(.await),
+                connection: quote!(redis::aio::ConnectionLike),
+                query: quote!(query_async),
+                suffix: "_async",
+            }
+        } else {
+            Flavor {
+                asyncness: quote!(),
+                dot_await: quote!(),
+                connection: quote!(redis::ConnectionLike),
+                query: quote!(query),
+                suffix: "",
+            }
+        }
+    }
+}
+
+/// `save`, `save_with_ttl`, `load`, `delete`, `exists` and per-field
+/// `load_<field>`/`save_<field>`, either blocking or as `async fn ..._async`.
+fn persistence_methods(
+    is_async: bool,
+    attrs: &ParsedAttributeMap,
+    fields: &[(&Field, FieldAttributes)],
+) -> syn::Result<TokenStream> {
+    let Flavor {
+        asyncness,
+        dot_await,
+        connection,
+        query,
+        suffix,
+    } = Flavor::new(is_async);
+
+    let save = format_ident!("save{}", suffix);
+    let save_with_ttl = format_ident!("save_with_ttl{}", suffix);
+    let load = format_ident!("load{}", suffix);
+    let delete = format_ident!("delete{}", suffix);
+    let exists = format_ident!("exists{}", suffix);
+
+    let field_methods = fields.iter().map(|(field, field_attrs)| {
+        // A flattened field has no hash field of its own to read or write.
+        if field_attrs.flatten {
+            return Ok(quote!());
+        }
+        let field_ident = field.ident.as_ref().expect("Named field should have ident");
+        let field_ty = &field.ty;
+        let field_name = util::transform_field_name(
+            &field_ident.to_string(),
+            attrs.rename_all.as_ref(),
+            field_attrs.rename.as_ref(),
+        );
+        let load_field = format_ident!("load_{}{}", field_ident, suffix);
+        let save_field = format_ident!("save_{}{}", field_ident, suffix);
+
+        let loader = (!field_attrs.skips_deserializing()).then(|| {
+            let returned = if util::is_option(field_ty) {
+                quote!(#field_ty)
+            } else {
+                quote!(Option<#field_ty>)
+            };
+            let doc = format!("Read only the `{field_name}` field of the hash at `key`.");
+            let body = if field_attrs.json
+                || field_attrs.as_string
+                || field_attrs.deserialize_with.is_some()
+            {
+                let decoded = decoded_value(field_attrs, field_ty, quote!(&value));
+                let found = if util::is_option(field_ty) {
+                    decoded
+                } else {
+                    quote!(#decoded.map(Some))
+                };
+                quote! {
+                    let value: redis::Value =
+                        redis::cmd("HGET").arg(key).arg(#field_name).#query(con)#dot_await?;
+                    match value {
+                        redis::Value::Nil => Ok(None),
+                        value => #found,
+                    }
+                }
+            } else {
+                quote!(redis::cmd("HGET").arg(key).arg(#field_name).#query(con)#dot_await)
+            };
+            quote! {
+                #[doc = #doc]
+                #[allow(dead_code)]
+                pub #asyncness fn #load_field(
+                    con: &mut impl #connection,
+                    key: impl redis::ToRedisArgs,
+                ) -> redis::RedisResult<#returned> {
+                    #body
+                }
+            }
+        });
+
+        let saver = (!field_attrs.skips_serializing()).then(|| -> syn::Result<_> {
+            let mut doc = format!(
+                "Write only the `{field_name}` field of the hash at `key` (`HDEL` when `value` has no args)."
+            );
+            // As in `write_redis_args`, a `serialize_with` function is called only once.
+            let (encode, encoded_attrs, value) = match &field_attrs.serialize_with {
+                Some(path) => {
+                    let encoded_attrs = FieldAttributes {
+                        serialize_with: None,
+                        ..field_attrs.clone()
+                    };
+                    (quote!(let value = &#path(value);), encoded_attrs, quote!(value))
+                }
+                None => (quote!(), field_attrs.clone(), quote!(value)),
+            };
+            let has_value = has_value(&encoded_attrs, field_ty, value.clone());
+            let value = encoded_value(&encoded_attrs, field_ty, &field_name, value);
+
+            // An HSET clears the field's TTL, so an expiring field is written together
+            // with its expiry in one MULTI block.
+            let (expire_param, expire_command) = if let Some(seconds) = field_attrs.expire {
+                doc.push_str(&format!(" The field expires after {seconds} seconds."));
+                let command = quote! {
+                    pipe.cmd("HEXPIRE")
+                        .arg(&key)
+                        .arg(#seconds)
+                        .arg("FIELDS")
+                        .arg(1)
+                        .arg(#field_name)
+                        .ignore();
+                };
+                (quote!(), command)
+            } else if let Some(expire_at) = &field_attrs.expire_at {
+                let source_field = expire_at_source(expire_at, fields)?;
+                let source_ty = &source_field.ty;
+                doc.push_str(&format!(
+                    " The field expires at `expire_at`, the value of the `{}` field.",
+                    expire_at.field
+                ));
+                let command =
+                    expire_at_command(expire_at, &field_name, source_field, quote!(expire_at));
+                (quote!(expire_at: &#source_ty,), command)
+            } else {
+                return Ok(quote! {
+                    #[doc = #doc]
+                    #[allow(dead_code)]
+                    pub #asyncness fn #save_field(
+                        con: &mut impl #connection,
+                        key: impl redis::ToRedisArgs,
+                        value: &#field_ty,
+                    ) -> redis::RedisResult<()> {
+                        use redis::ToRedisArgs;
+
+                        #encode
+                        if #has_value {
+                            redis::cmd("HSET").arg(key).arg(#field_name).arg(#value).#query(con)#dot_await
+                        } else {
+                            redis::cmd("HDEL").arg(key).arg(#field_name).#query(con)#dot_await
+                        }
+                    }
+                });
+            };
+
+            Ok(quote! {
+                #[doc = #doc]
+                #[allow(dead_code)]
+                pub #asyncness fn #save_field(
+                    con: &mut impl #connection,
+                    key: impl redis::ToRedisArgs,
+                    value: &#field_ty,
+                    #expire_param
+                ) -> redis::RedisResult<()> {
+                    use redis::ToRedisArgs;
+
+                    #encode
+                    let mut pipe = redis::pipe();
+                    pipe.atomic();
+                    if #has_value {
+                        pipe.cmd("HSET").arg(&key).arg(#field_name).arg(#value);
+                        #expire_command
+                    } else {
+                        pipe.cmd("HDEL").arg(&key).arg(#field_name);
+                    }
+                    pipe.#query(con)#dot_await
+                }
+            })
+        });
+        let saver = saver.transpose()?;
+
+        Ok(quote! {
+            #loader
+            #saver
+        })
+    });
+    let field_methods = field_methods.collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        /// Store this value with a single atomic `HSET`, clearing omitted fields with
+        /// `HDEL` and applying field expiries and `KEY_TTL`.
+        #[allow(dead_code)]
+        pub #asyncness fn #save(&self, con: &mut impl #connection) -> redis::RedisResult<()> {
+            self.#save_with_ttl(con, Self::KEY_TTL)#dot_await
         }
+
+        /// Like `save`, but expires the key after `ttl` instead of `KEY_TTL`.
+        #[allow(dead_code)]
+        pub #asyncness fn #save_with_ttl(
+            &self,
+            con: &mut impl #connection,
+            ttl: Option<std::time::Duration>,
+        ) -> redis::RedisResult<()> {
+            self.save_pipeline(ttl).#query(con)#dot_await
+        }
+
+        /// Load the value stored at `key`, or `None` when the hash does not exist.
+        #[allow(dead_code)]
+        pub #asyncness fn #load(
+            con: &mut impl #connection,
+            key: impl redis::ToRedisArgs,
+        ) -> redis::RedisResult<Option<Self>>
+        where
+            Self: redis::FromRedisValue,
+        {
+            let value: redis::Value = redis::cmd("HGETALL").arg(key).#query(con)#dot_await?;
+            match &value {
+                redis::Value::Array(items) if items.is_empty() => Ok(None),
+                redis::Value::Map(items) if items.is_empty() => Ok(None),
+                _ => redis::FromRedisValue::from_redis_value(&value).map(Some),
+            }
+        }
+
+        /// Delete the hash at `key`, returning whether it existed.
+        #[allow(dead_code)]
+        pub #asyncness fn #delete(
+            con: &mut impl #connection,
+            key: impl redis::ToRedisArgs,
+        ) -> redis::RedisResult<bool> {
+            redis::cmd("DEL").arg(key).#query(con)#dot_await
+        }
+
+        /// Whether a hash exists at `key`.
+        #[allow(dead_code)]
+        pub #asyncness fn #exists(
+            con: &mut impl #connection,
+            key: impl redis::ToRedisArgs,
+        ) -> redis::RedisResult<bool> {
+            redis::cmd("EXISTS").arg(key).#query(con)#dot_await
+        }
+
+        #(#field_methods)*
+    })
+}
+
+/// Client-side caching for `#[redis(cache)]`: `load_cached` keeps decoded values in a
+/// per-type in-process cache keyed by the raw key bytes. Entries are dropped by
+/// `CLIENT TRACKING` invalidation pushes fed to `handle_push` and, with `ttl`, once
+/// they are older than `ttl` seconds. Every invalidation bumps a generation counter, and
+/// a load only caches its reply if no invalidation happened while it was in flight.
+fn cache_methods(
+    type_ident: &Ident,
+    generics: &Generics,
+    attrs: &ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            type_ident.span(),
+            "`cache` is not supported on generic structs",
+        ));
     }
-}
\ No newline at end of file
+
+    let is_fresh = match attrs.ttl {
+        Some(seconds) => quote!(cached_at.elapsed() < std::time::Duration::from_secs(#seconds)),
+        None => quote!(true),
+    };
+
+    let sync_methods = cached_loader(false);
+    let async_methods = if cfg!(feature = "async") {
+        cached_loader(true)
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        fn cache_entries() -> &'static std::sync::Mutex<
+            std::collections::HashMap<Vec<u8>, (std::time::Instant, #type_ident)>,
+        > {
+            static ENTRIES: std::sync::OnceLock<
+                std::sync::Mutex<
+                    std::collections::HashMap<Vec<u8>, (std::time::Instant, #type_ident)>,
+                >,
+            > = std::sync::OnceLock::new();
+            ENTRIES.get_or_init(Default::default)
+        }
+
+        /// Bumped, with the entries locked, by every invalidation.
+        fn cache_generation() -> &'static std::sync::atomic::AtomicU64 {
+            static GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+            &GENERATION
+        }
+
+        fn cache_invalidated() {
+            Self::cache_generation().fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        fn cache_lookup(cache_key: &[u8]) -> Option<Self>
+        where
+            Self: Clone,
+        {
+            let mut entries = Self::cache_entries()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            match entries.get(cache_key) {
+                Some((cached_at, value)) if #is_fresh => Some(value.clone()),
+                Some(_) => {
+                    entries.remove(cache_key);
+                    None
+                }
+                None => None,
+            }
+        }
+
+        /// Cache `value` unless an invalidation happened since `generation` was read.
+        fn cache_store(cache_key: Vec<u8>, generation: u64, value: &Option<Self>)
+        where
+            Self: Clone,
+        {
+            let Some(value) = value else {
+                return;
+            };
+            let mut entries = Self::cache_entries()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if Self::cache_generation().load(std::sync::atomic::Ordering::SeqCst) == generation {
+                entries.insert(cache_key, (std::time::Instant::now(), value.clone()));
+            }
+        }
+
+        /// Drop the cached value for `key`, if any.
+        #[allow(dead_code)]
+        pub fn invalidate_cached(key: impl redis::ToRedisArgs) {
+            let mut entries = Self::cache_entries()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            entries.remove(&redis::ToRedisArgs::to_redis_args(&key).concat());
+            Self::cache_invalidated();
+        }
+
+        /// Drop every cached value of this type.
+        #[allow(dead_code)]
+        pub fn clear_cache() {
+            let mut entries = Self::cache_entries()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            entries.clear();
+            Self::cache_invalidated();
+        }
+
+        /// Apply a RESP3 push message to the cache: `invalidate` drops the listed keys
+        /// (or everything after a flush), and a disconnection drops everything since
+        /// tracking is lost with the connection. Other pushes are ignored.
+        #[allow(dead_code)]
+        pub fn handle_push(push: &redis::PushInfo) {
+            let mut entries = Self::cache_entries()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            match push.kind {
+                redis::PushKind::Invalidate => match push.data.first() {
+                    Some(redis::Value::Array(keys)) => {
+                        for key in keys {
+                            if let redis::Value::BulkString(key) = key {
+                                entries.remove(key);
+                            }
+                        }
+                    }
+                    _ => entries.clear(),
+                },
+                redis::PushKind::Disconnection => entries.clear(),
+                _ => return,
+            }
+            Self::cache_invalidated();
+        }
+
+        #sync_methods
+        #async_methods
+    })
+}
+
+/// `enable_tracking` and `load_cached`, either blocking or as `async fn ..._async`.
+fn cached_loader(is_async: bool) -> TokenStream {
+    let Flavor {
+        asyncness,
+        dot_await,
+        connection,
+        query,
+        suffix,
+    } = Flavor::new(is_async);
+
+    let enable_tracking = format_ident!("enable_tracking{}", suffix);
+    let load_cached = format_ident!("load_cached{}", suffix);
+
+    quote! {
+        /// Turn on `CLIENT TRACKING` for `con` so Redis sends invalidation pushes for
+        /// keys read through it. Requires a RESP3 connection.
+        #[allow(dead_code)]
+        pub #asyncness fn #enable_tracking(con: &mut impl #connection) -> redis::RedisResult<()> {
+            redis::cmd("CLIENT").arg("TRACKING").arg("ON").#query(con)#dot_await
+        }
+
+        /// Like `load`, but served from the in-process cache while the entry is fresh.
+        /// Missing hashes are not cached.
+        #[allow(dead_code)]
+        pub #asyncness fn #load_cached(
+            con: &mut impl #connection,
+            key: impl redis::ToRedisArgs,
+        ) -> redis::RedisResult<Option<Self>>
+        where
+            Self: Clone + redis::FromRedisValue,
+        {
+            let cache_key = redis::ToRedisArgs::to_redis_args(&key).concat();
+            if let Some(value) = Self::cache_lookup(&cache_key) {
+                return Ok(Some(value));
+            }
+
+            // Read before the fetch, so an invalidation arriving meanwhile skips the store.
+            let generation = Self::cache_generation().load(std::sync::atomic::Ordering::SeqCst);
+            let value: redis::Value = redis::cmd("HGETALL").arg(key).#query(con)#dot_await?;
+            let loaded = match &value {
+                redis::Value::Array(items) if items.is_empty() => None,
+                redis::Value::Map(items) if items.is_empty() => None,
+                _ => Some(redis::FromRedisValue::from_redis_value(&value)?),
+            };
+            Self::cache_store(cache_key, generation, &loaded);
+            Ok(loaded)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::Data;
+
+    /// The error `derive_to_redis_struct` reports for `input`.
+    fn derive_error(input: syn::DeriveInput) -> String {
+        let Data::Struct(data_struct) = input.data else {
+            panic!("expected a struct");
+        };
+        let attrs = util::parse_attributes(&input.attrs).unwrap();
+        derive_to_redis_struct(data_struct, input.ident, input.vis, input.generics, attrs)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_check_field_helper_names() {
+        let cached: syn::DeriveInput = syn::parse_quote! {
+            #[redis(key = "page:{id}", repository, cache)]
+            struct Page {
+                id: u64,
+                cached: bool,
+            }
+        };
+        assert_eq!(
+            derive_error(cached),
+            "field `cached` would generate `load_cached`, which `cache` already generates; rename the field"
+        );
+
+        let with_ttl: syn::DeriveInput = syn::parse_quote! {
+            #[redis(key = "order:{id}", repository)]
+            struct Order {
+                id: u64,
+                with_ttl: bool,
+            }
+        };
+        assert_eq!(
+            derive_error(with_ttl),
+            "field `with_ttl` would generate `save_with_ttl`, which `repository` already generates; rename the field"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use crate::util::{self, Format, ParsedAttributeMap};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Generics, Ident, Type};
+
+/// `ToRedisArgs` for `#[redis(format = "...")]`: the whole value as a single argument.
+pub fn derive_to_redis_format(
+    format: Format,
+    type_ident: Ident,
+    generics: Generics,
+    attrs: ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let (name, encode) = match format {
+        Format::Json => ("JSON", quote!(serde_json::to_vec(self))),
+        Format::MessagePack => ("MessagePack", quote!(rmp_serde::to_vec_named(self))),
+        Format::Bincode => (
+            "bincode",
+            quote! {
+                bincode::serde::encode_to_vec(self, bincode::config::standard())
+            },
+        ),
+    };
+
+    let generics = serde_bounds(generics, quote!(serde::Serialize), &attrs);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
+            fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
+                let encoded = #encode.unwrap_or_else(|e| {
+                    panic!("Failed to serialize {} as {}: {}", stringify!(#type_ident), #name, e)
+                });
+                out.write_arg(&encoded);
+            }
+
+            fn num_of_args(&self) -> usize {
+                1 // The encoded value is always a single argument
+            }
+        }
+    })
+}
+
+/// `FromRedisValue` for `#[redis(format = "...")]`: decode the bytes of a bulk or
+/// verbatim string.
+pub fn derive_from_redis_format(
+    format: Format,
+    type_ident: Ident,
+    generics: Generics,
+    attrs: ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let (name, decode) = match format {
+        Format::Json => ("JSON", quote!(serde_json::from_slice(bytes))),
+        Format::MessagePack => ("MessagePack", quote!(rmp_serde::from_slice(bytes))),
+        Format::Bincode => (
+            "bincode",
+            quote! {
+                bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                    .map(|(value, _)| value)
+            },
+        ),
+    };
+    let invalid = format!("Invalid {name}");
+    let kind = util::value_kind(quote!(v));
+
+    let generics = serde_bounds(generics, quote!(serde::de::DeserializeOwned), &attrs);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                let bytes: &[u8] = match v {
+                    redis::Value::BulkString(data) => data,
+                    redis::Value::VerbatimString { text, .. } => text.as_bytes(),
+                    redis::Value::Nil => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Cannot deserialize from nil value",
+                            format!("Expected {} value for {}, got nil", #name, stringify!(#type_ident)),
+                        )));
+                    }
+                    _ => {
+                        return Err(redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Unexpected Redis value type",
+                            format!(
+                                "Expected {} value for {}, got {}",
+                                #name,
+                                stringify!(#type_ident),
+                                #kind
+                            ),
+                        )));
+                    }
+                };
+
+                #decode.map_err(|e| {
+                    redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        #invalid,
+                        format!("{}: {}", stringify!(#type_ident), e),
+                    ))
+                })
+            }
+        }
+    })
+}
+
+/// `ToRedisArgs` for `#[redis(as_string)]`: the value's `Display` output as a single
+/// argument.
+pub fn derive_to_redis_string(
+    type_ident: Ident,
+    generics: Generics,
+    attrs: ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let generics = self_string_bounds(&type_ident, generics, false, &attrs);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::ToRedisArgs for #type_ident #ty_generics #where_clause {
+            fn write_redis_args<W: ?Sized + redis::RedisWrite>(&self, out: &mut W) {
+                out.write_arg_fmt(self);
+            }
+
+            fn num_of_args(&self) -> usize {
+                1
+            }
+        }
+    })
+}
+
+/// `FromRedisValue` for `#[redis(as_string)]`: read a string and `parse` it.
+pub fn derive_from_redis_string(
+    type_ident: Ident,
+    generics: Generics,
+    attrs: ParsedAttributeMap,
+) -> syn::Result<TokenStream> {
+    let generics = self_string_bounds(&type_ident, generics, true, &attrs);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics redis::FromRedisValue for #type_ident #ty_generics #where_clause {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                let text = <String as redis::FromRedisValue>::from_redis_value(v)?;
+                text.parse().map_err(|e| {
+                    redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Invalid value",
+                        format!("{} {:?}: {}", stringify!(#type_ident), text, e),
+                    ))
+                })
+            }
+        }
+    })
+}
+
+/// Bound the type itself by `Display` or, with `parse`, `FromStr`, unless
+/// `#[redis(bound = "...")]` replaces the inferred bounds.
+fn self_string_bounds(
+    type_ident: &Ident,
+    generics: Generics,
+    parse: bool,
+    attrs: &ParsedAttributeMap,
+) -> Generics {
+    if attrs.bound.is_some() {
+        return util::add_trait_bounds(generics, &[], quote!(), attrs.bound.as_ref());
+    }
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let self_ty: Type = parse_quote!(#type_ident #ty_generics);
+    util::string_bounds(generics, &[&self_ty], parse)
+}
+
+/// Bound every type parameter by `bound`, unless `#[redis(bound = "...")]` replaces the
+/// inferred bounds.
+fn serde_bounds(generics: Generics, bound: TokenStream, attrs: &ParsedAttributeMap) -> Generics {
+    let params: Vec<Type> = generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            parse_quote!(#ident)
+        })
+        .collect();
+    let params: Vec<&Type> = params.iter().collect();
+    util::add_trait_bounds(generics, &params, bound, attrs.bound.as_ref())
+}
@@ -15,6 +15,8 @@ Initial development was done by @Michaelvanstraten 🙏🏽.
 - **RESP3 Support**: Native support for Redis 7+ protocol features including VerbatimString
 - **Hash Field Expiration**: Per-field TTL support using Redis 7.4+ HEXPIRE commands  
 - **Client-Side Caching**: Automatic cache management with Redis 6+ client caching
+- **Enums with Data**: Tuple and struct variants stored as tagged or untagged hashes
+- **Serde Formats**: Optional JSON fields and whole values as JSON, MessagePack or bincode
 - **Cluster Awareness**: Hash tag generation for Redis Cluster deployments
 - **Flexible Naming**: Support for various case conversion rules (snake_case, kebab-case, etc.)
 - **Comprehensive Error Handling**: Clear error messages for debugging
@@ -38,7 +40,7 @@ use redis_derive::{FromRedisValue, ToRedisArgs};
 
 ### Basic Struct Example
 
-```rust
+```rust,no_run
 use redis::Commands;
 use redis_derive::{FromRedisValue, ToRedisArgs};
 
@@ -62,10 +64,10 @@ fn main() -> redis::RedisResult<()> {
     };
 
     // Store individual fields
-    con.hset("user:12345", "id", user.id)?;
-    con.hset("user:12345", "username", &user.username)?;
-    con.hset("user:12345", "email", &user.email)?;
-    con.hset("user:12345", "active", user.active)?;
+    let _: () = con.hset("user:12345", "id", user.id)?;
+    let _: () = con.hset("user:12345", "username", &user.username)?;
+    let _: () = con.hset("user:12345", "email", &user.email)?;
+    let _: () = con.hset("user:12345", "active", user.active)?;
 
     // Retrieve the complete struct
     let retrieved_user: User = con.hgetall("user:12345")?;
@@ -96,6 +98,118 @@ let retrieved: UserRole = con.get("user:role")?;
 assert_eq!(role, retrieved);
 ```
 
+### Enums with Data
+
+Enums with tuple or struct variants are stored as hashes. The variant is recorded in
+one of four ways, following serde's enum representations:
+
+- **Externally tagged** (default): payload fields are keyed by the variant name, e.g.
+  `circle.radius`; a newtype variant stores a single value under the variant name
+  itself and nests the fields of a struct under it (`circle.radius` for
+  `Circle(Circle)`), and a unit variant stores an empty value there. A variant whose payload writes
+  nothing, such as `Login { ip: None }`, stores an empty `login.` field instead.
+- **Internally tagged** (`#[redis(tag = "type")]`): the variant name goes into the
+  `type` field, next to the variant's own fields. A newtype variant writes its payload's
+  fields, so it must wrap a struct or another type stored as fields.
+- **Adjacently tagged** (`#[redis(tag = "t", content = "c")]`): the variant name goes into
+  `t` and the payload is keyed by `c` (`c` for a newtype of a single value, `c.x` for
+  fields, including those of a struct wrapped by a newtype).
+- **Untagged** (`#[redis(untagged)]`): only the payload is written; reading tries each
+  variant in order and takes the first that decodes. As with `tag`, a newtype variant
+  must wrap a type stored as fields.
+
+Tuple fields are named by their index. Variant fields accept `rename`, `skip*` and
+`default`; `rename_all` applies to the variant names.
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(tag = "type", rename_all = "snake_case")]
+enum Event {
+    Login { user: String, ip: Option<String> },
+    Logout { user: String },
+    Heartbeat,
+}
+
+let event = Event::Login { user: "alice".to_string(), ip: None };
+// HSET event:1 type login user alice
+assert_eq!(
+    event.to_redis_args(),
+    vec![b"type".to_vec(), b"login".to_vec(), b"user".to_vec(), b"alice".to_vec()]
+);
+
+let stored = Value::Array(event.to_redis_args().into_iter().map(Value::BulkString).collect());
+assert_eq!(Event::from_redis_value(&stored).unwrap(), event);
+```
+
+An untagged unit variant is read from an empty hash or from nil (a missing key). A
+value that is not a hash, such as an integer, matches none of the variants stored as
+fields:
+
+```rust
+use redis::{FromRedisValue, Value};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(untagged)]
+enum Setting {
+    Custom { level: u8 },
+    Unset,
+}
+
+let custom = Value::Array(vec![Value::BulkString(b"level".to_vec()), Value::BulkString(b"3".to_vec())]);
+assert_eq!(Setting::from_redis_value(&custom).unwrap(), Setting::Custom { level: 3 });
+assert_eq!(Setting::from_redis_value(&Value::Array(vec![])).unwrap(), Setting::Unset);
+assert_eq!(Setting::from_redis_value(&Value::Nil).unwrap(), Setting::Unset);
+assert!(Setting::from_redis_value(&Value::Int(5)).is_err());
+```
+
+### Whole-Value Formats
+
+To keep a type under a plain `SET key value` (or in a list, or in a pub/sub payload)
+instead of a hash, `#[redis(format = "...")]` encodes the whole struct or enum as a
+single argument with serde. Reading accepts the bulk or verbatim string it was written
+as. Each format has a cargo feature of the same name, and your crate depends on the
+matching serde library:
+
+- `format = "json"`: `serde_json`
+- `format = "msgpack"`: `rmp-serde` (structs are written as maps)
+- `format = "bincode"`: `bincode` 2 with its `serde` feature
+
+```rust
+# #[cfg(feature = "json")]
+# {
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(format = "json")]
+struct Event {
+    kind: String,
+    ids: Vec<u64>,
+}
+
+let event = Event { kind: "login".to_string(), ids: vec![1, 2] };
+let args = event.to_redis_args();
+assert_eq!(args, vec![br#"{"kind":"login","ids":[1,2]}"#.to_vec()]);
+
+let value = redis::Value::BulkString(args[0].clone());
+assert_eq!(Event::from_redis_value(&value).unwrap(), event);
+# }
+```
+
+Hash-specific attributes such as `key`, `repository` or `tag` cannot be combined with
+`format`; use serde's own attributes to shape the encoding.
+
+#### Panics
+
+`ToRedisArgs` cannot report errors, so writing a value the serde library fails to
+encode, such as a map whose keys are not strings in JSON, panics. The same applies to
+`#[redis(json)]` fields.
+
 ## Naming Conventions and Attributes
 
 ### Case Conversion Rules
@@ -148,6 +262,649 @@ let retrieved: UserRole = con.get("key")?;
 // "Unknown variant 'admin' for UserRole. Valid variants: [administrator, power_user, regular_user, guest_user]"
 ```
 
+### Variant Renames and Aliases
+
+`#[redis(rename = "...")]` on a variant overrides `rename_all` for that variant, and
+`#[redis(alias = "...")]` (repeatable) accepts further spellings when reading. Values
+are always written with the primary name, so legacy spellings can be migrated without
+breaking older data:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case")]
+enum Plan {
+    #[redis(rename = "pro", alias = "premium", alias = "gold")]
+    Professional,
+    Free,
+}
+
+assert_eq!(Plan::Professional.to_redis_args(), vec![b"pro".to_vec()]);
+let legacy = Value::BulkString(b"premium".to_vec());
+assert_eq!(Plan::from_redis_value(&legacy).unwrap(), Plan::Professional);
+
+// "Unknown variant 'trial' for Plan. Valid variants: [pro, premium, gold, free]"
+assert!(Plan::from_redis_value(&Value::BulkString(b"trial".to_vec())).is_err());
+```
+
+The same attributes apply to the variant names of enums with data.
+
+### Unknown Variants
+
+By default an unknown value is an "Unknown enum variant" error. Mark one variant with
+`#[redis(other)]` to accept anything else instead, so older binaries keep working when a
+newer deployment writes a new value:
+
+- On a unit variant the unknown value is dropped; writing the variant writes its own name.
+- On a newtype variant such as `Unknown(String)` the raw value is kept and written back
+  unchanged.
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case")]
+enum Status {
+    Active,
+    Suspended,
+    #[redis(other)]
+    Unknown(String),
+}
+
+let newer = Value::BulkString(b"archived".to_vec());
+let status = Status::from_redis_value(&newer).unwrap();
+assert_eq!(status, Status::Unknown("archived".to_string()));
+assert_eq!(status.to_redis_args(), vec![b"archived".to_vec()]);
+```
+
+In an enum with data, a unit `other` variant catches unknown values of the `tag` field.
+
+### Lenient Variant Matching
+
+Values written by other tooling may not use the exact spelling. `#[redis(case_insensitive)]`
+matches variant names and aliases ASCII-case-insensitively, and `#[redis(trim)]` ignores
+surrounding whitespace. Neither allocates, and values are still written in their canonical
+form:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case", case_insensitive, trim)]
+enum State {
+    Active,
+    OnHold,
+}
+
+let shouted = Value::BulkString(b"ON_HOLD ".to_vec());
+assert_eq!(State::from_redis_value(&shouted).unwrap(), State::OnHold);
+assert_eq!(State::OnHold.to_redis_args(), vec![b"on_hold".to_vec()]);
+```
+
+Both attributes also apply to the `tag` field of enums with data.
+
+### Integer Discriminants
+
+`#[redis(repr = "int")]` stores a unit enum as its discriminant instead of its name,
+written with the integer type from `#[repr(...)]` (`isize` if there is none). Reading
+accepts integer replies, numeric strings and whole RESP3 doubles; a code that matches no
+variant is an "Unknown enum discriminant" error, or the `other` variant if there is one:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[repr(u8)]
+#[redis(repr = "int")]
+enum Priority {
+    Low = 1,
+    Normal = 5,
+    High = 10,
+}
+
+assert_eq!(Priority::High.to_redis_args(), vec![b"10".to_vec()]);
+assert_eq!(Priority::from_redis_value(&Value::Int(5)).unwrap(), Priority::Normal);
+assert_eq!(Priority::from_redis_value(&Value::BulkString(b"1".to_vec())).unwrap(), Priority::Low);
+assert!(Priority::from_redis_value(&Value::Int(7)).is_err());
+```
+
+### Boolean and Integer Values
+
+A unit variant marked `#[redis(value = 1)]` or `#[redis(value = true)]` is written as that
+value rather than its name (booleans as `1` and `0`, like Redis itself). Reading then also
+accepts RESP3 `Boolean`, `Int` and whole `Double` replies, so a two-state enum round-trips
+through flags set by scripts or other clients. The name is still accepted when reading:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use redis::{FromRedisValue, ToRedisArgs, Value};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "lowercase")]
+enum Switch {
+    #[redis(value = true)]
+    On,
+    #[redis(value = false)]
+    Off,
+}
+
+assert_eq!(Switch::On.to_redis_args(), vec![b"1".to_vec()]);
+assert_eq!(Switch::from_redis_value(&Value::Boolean(false)).unwrap(), Switch::Off);
+assert_eq!(Switch::from_redis_value(&Value::Int(1)).unwrap(), Switch::On);
+assert_eq!(Switch::from_redis_value(&Value::BulkString(b"off".to_vec())).unwrap(), Switch::Off);
+```
+
+Replies of any other type are rejected with an error naming the `redis::Value` variant
+that was received.
+
+### String Conversions
+
+`#[redis(strings)]` on a unit enum makes the names written to Redis available without
+serialising. Alongside `ToRedisArgs` it generates `Display`, `FromStr` (which accepts the
+same names, aliases and spellings as `FromRedisValue`), `as_redis_str()` and a `VARIANTS`
+list, all from the same names, so logs, APIs and Lua scripts never drift from what is
+stored:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case", strings)]
+enum State {
+    Active,
+    OnHold,
+}
+
+assert_eq!(State::OnHold.as_redis_str(), "on_hold");
+assert_eq!(State::Active.to_string(), "active");
+assert_eq!(State::VARIANTS, &["active", "on_hold"]);
+assert_eq!("on_hold".parse::<State>().unwrap(), State::OnHold);
+assert!("paused".parse::<State>().is_err());
+```
+
+### Generic Types
+
+Type parameters, lifetimes, const generics and `where` clauses are carried over to
+the generated impls. Every type parameter used by a field gets a `ToRedisArgs` /
+`FromRedisValue` bound automatically:
+
+```rust
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Page<T> {
+    items: T,
+    cursor: u64,
+}
+
+let page = Page { items: "a,b,c".to_string(), cursor: 3 };
+let args = page.to_redis_args();
+let value = redis::Value::Array(args.into_iter().map(redis::Value::BulkString).collect());
+assert_eq!(Page::<String>::from_redis_value(&value).unwrap(), page);
+```
+
+When the inferred bounds are not what you need, replace them with
+`#[redis(bound = "...")]`:
+
+```rust
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+#[redis(bound = "T: redis::ToRedisArgs + Clone")]
+struct Tagged<T> {
+    value: T,
+}
+```
+
+### Optional Fields and Defaults
+
+Hash keys missing from an `HGETALL` reply decode to `None` for `Option` fields, so
+adding an optional field does not break reading existing hashes. Other fields can
+opt in with `#[redis(default)]` (uses `Default::default()`) or
+`#[redis(default = "path::to_fn")]`. Putting `#[redis(default)]` on the struct fills
+every missing field from the struct's own `Default` impl:
+
+```rust
+use redis::FromRedisValue;
+use redis_derive::FromRedisValue;
+
+fn default_plan() -> String {
+    "free".to_string()
+}
+
+#[derive(FromRedisValue, Debug, PartialEq)]
+struct Account {
+    id: u64,
+    nickname: Option<String>, // added later, absent in old hashes
+    #[redis(default)]
+    logins: u32,
+    #[redis(default = "default_plan")]
+    plan: String,
+}
+
+let old_hash = redis::Value::Array(vec![
+    redis::Value::BulkString(b"id".to_vec()),
+    redis::Value::BulkString(b"1".to_vec()),
+]);
+let account = Account::from_redis_value(&old_hash).unwrap();
+assert_eq!(
+    account,
+    Account { id: 1, nickname: None, logins: 0, plan: "free".to_string() }
+);
+```
+
+On a generic struct, the container `#[redis(default)]` bounds the impl by the struct's
+own `Default`:
+
+```rust
+use redis::FromRedisValue;
+use redis_derive::FromRedisValue;
+
+#[derive(FromRedisValue, Default, Debug, PartialEq)]
+#[redis(default)]
+struct Page<T> {
+    items: T,
+    cursor: u64,
+}
+
+let first = redis::Value::Array(vec![
+    redis::Value::BulkString(b"items".to_vec()),
+    redis::Value::BulkString(b"3".to_vec()),
+]);
+assert_eq!(Page::<u32>::from_redis_value(&first).unwrap(), Page { items: 3, cursor: 0 });
+```
+
+Any other missing field is reported as a `Missing required field` error.
+
+### Omitted Fields When Writing
+
+A field is written only when its value produces at least one argument, so `None`
+(or an empty value) never leaves a dangling field name in an `HSET` argument list.
+`#[redis(skip_serializing_if = "path")]` omits a field whenever the predicate returns
+`true`. With `#[redis(omitted_fields)]` (implied by `repository`), the generated
+`omitted_fields()` method lists the fields left out by a write, ready for an `HDEL` that
+clears previously stored values:
+
+```rust
+use redis::ToRedisArgs;
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+#[redis(omitted_fields)]
+struct Profile {
+    id: u64,
+    email: Option<String>,
+    #[redis(skip_serializing_if = "String::is_empty")]
+    bio: String,
+}
+
+let profile = Profile { id: 1, email: None, bio: String::new() };
+assert_eq!(profile.to_redis_args(), vec![b"id".to_vec(), b"1".to_vec()]);
+assert_eq!(profile.omitted_fields(), vec!["email", "bio"]);
+```
+
+### Flattened Structs
+
+A field whose type writes field/value pairs of its own, such as another derived struct
+or a `HashMap<String, String>`, can be stored in the parent's hash with
+`#[redis(flatten)]`. `#[redis(flatten, prefix = "addr.")]` prefixes each nested field
+name, and reading collects the entries under the prefix back into the field. Without a
+prefix the field receives every entry the rest of the struct doesn't claim:
+
+```rust
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::collections::HashMap;
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct User {
+    name: String,
+    #[redis(flatten, prefix = "addr.")]
+    address: Address,
+    #[redis(flatten)]
+    extra: HashMap<String, String>,
+}
+
+let user = User {
+    name: "Ada".to_string(),
+    address: Address { street: "1 Main St".to_string(), city: "London".to_string() },
+    extra: HashMap::from([("team".to_string(), "core".to_string())]),
+};
+let args = user.to_redis_args();
+assert_eq!(&args[..4], &[b"name".to_vec(), b"Ada".to_vec(), b"addr.street".to_vec(), b"1 Main St".to_vec()]);
+
+let value = redis::Value::Array(args.into_iter().map(redis::Value::BulkString).collect());
+assert_eq!(User::from_redis_value(&value).unwrap(), user);
+```
+
+A flattened `Option` field is `None` when no entry matches it. Fields the nested value
+omits are not listed by `omitted_fields()`.
+
+### JSON Fields
+
+A `Vec`, map or nested type has no single-field representation of its own. With the
+`json` cargo feature, `#[redis(json)]` stores such a field as one JSON string encoded
+with `serde_json`, which your crate depends on together with `serde`:
+
+```toml
+[dependencies]
+redis-derive = { version = "0.2.0", features = ["json"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+```
+
+```rust
+# #[cfg(feature = "json")]
+# {
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Preferences {
+    theme: String,
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct User {
+    id: u64,
+    #[redis(json)]
+    tags: Vec<String>,
+    #[redis(json)]
+    preferences: Option<Preferences>,
+}
+
+let user = User {
+    id: 1,
+    tags: vec!["admin".to_string()],
+    preferences: Some(Preferences { theme: "dark".to_string() }),
+};
+let args = user.to_redis_args();
+assert_eq!(args[3], br#"["admin"]"#.to_vec());
+
+let value = redis::Value::Array(args.into_iter().map(redis::Value::BulkString).collect());
+assert_eq!(User::from_redis_value(&value).unwrap(), user);
+# }
+```
+
+A `None` field is left out like any other. Invalid JSON fails with a
+`Failed to parse field` error naming the field and the line and column of the problem. Since
+`ToRedisArgs` cannot report errors, writing panics if `serde_json` fails to encode the
+field.
+
+### Custom Field Codecs
+
+For a field type without `ToRedisArgs`/`FromRedisValue`, or one that needs a different
+stored form, `#[redis(with = "module")]` calls the module's `write` and `read`
+functions instead of the redis traits:
+
+- `fn write(value: &T) -> impl ToRedisArgs`
+- `fn read(value: &redis::Value) -> redis::RedisResult<T>`
+
+```rust
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod unix_time {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub fn write(time: &SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+    }
+
+    pub fn read(value: &redis::Value) -> redis::RedisResult<SystemTime> {
+        let secs: u64 = redis::FromRedisValue::from_redis_value(value)?;
+        Ok(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Session {
+    #[redis(expire_at = "expires")]
+    token: String,
+    #[redis(with = "unix_time")]
+    expires: SystemTime,
+}
+
+let session = Session {
+    token: "abc".to_string(),
+    expires: UNIX_EPOCH + Duration::from_secs(1_900_000_000),
+};
+let args = session.to_redis_args();
+assert_eq!(args[3], b"1900000000".to_vec());
+
+let value = redis::Value::Array(args.into_iter().map(redis::Value::BulkString).collect());
+assert_eq!(Session::from_redis_value(&value).unwrap(), session);
+```
+
+Use `serialize_with = "path"` and `deserialize_with = "path"` to override one
+direction only. `write` returning no args (such as `None`) leaves the field out, and
+a `read` error is reported as `Failed to parse field` naming the field. The field
+type needs no redis trait bound for the overridden direction; on a generic field,
+state what the functions require with `#[redis(bound = "...")]`.
+
+The `write` function runs once per `write_redis_args`, `num_of_args` or
+`omitted_fields` call, however often the generated code checks and writes the field:
+
+```rust
+use redis::ToRedisArgs;
+use redis_derive::ToRedisArgs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn cents(price: &f64) -> i64 {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    (price * 100.0).round() as i64
+}
+
+#[derive(ToRedisArgs)]
+struct Item {
+    #[redis(serialize_with = "cents")]
+    price: f64,
+}
+
+let item = Item { price: 9.99 };
+assert_eq!(item.to_redis_args(), vec![b"price".to_vec(), b"999".to_vec()]);
+assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+```
+
+### Display and FromStr Types
+
+Types that already implement `Display` and `FromStr`, such as IDs, versions or
+addresses, can be stored as their string form. `#[redis(as_string)]` on a field
+writes `to_string()` and reads with `str::parse`; on a struct or enum it does the same
+for the whole value, which then implements the redis traits itself:
+
+```rust
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(as_string)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = s.split_once('.').ok_or("expected MAJOR.MINOR")?;
+        Ok(Version {
+            major: major.parse().map_err(|e| format!("{e}"))?,
+            minor: minor.parse().map_err(|e| format!("{e}"))?,
+        })
+    }
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Server {
+    #[redis(as_string)]
+    ip: Ipv4Addr,
+    #[redis(as_string)]
+    gateway: Option<Ipv4Addr>,
+    version: Version,
+}
+
+let server = Server {
+    ip: Ipv4Addr::new(10, 0, 0, 1),
+    gateway: None,
+    version: Version { major: 1, minor: 2 },
+};
+let args = server.to_redis_args();
+assert_eq!(args[1], b"10.0.0.1".to_vec());
+assert_eq!(args[3], b"1.2".to_vec());
+
+let value = redis::Value::Array(args.into_iter().map(redis::Value::BulkString).collect());
+assert_eq!(Server::from_redis_value(&value).unwrap(), server);
+
+let bad = redis::Value::Array(vec![
+    redis::Value::BulkString(b"ip".to_vec()),
+    redis::Value::BulkString(b"10.0.0.x".to_vec()),
+    redis::Value::BulkString(b"version".to_vec()),
+    redis::Value::BulkString(b"1.2".to_vec()),
+]);
+let err = Server::from_redis_value(&bad).unwrap_err().to_string();
+assert!(err.contains("Failed to parse field"));
+assert!(err.contains("Field 'ip'"));
+assert!(err.contains("invalid IPv4 address syntax"));
+```
+
+An `Option` field is left out when `None`. The `FromStr` error must implement
+`Display`; its message is reported together with the rejected string.
+
+### Skipping Fields
+
+Fields marked `#[redis(skip)]` are neither written nor read. When decoding they are
+initialised with `Default::default()`, or with the function given by
+`#[redis(skip, default = "path::to_fn")]`. Use `skip_serializing` or
+`skip_deserializing` to skip only one direction:
+
+```rust
+use redis::{FromRedisValue, ToRedisArgs};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+fn fresh_hits() -> u32 {
+    1
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Article {
+    id: u64,
+    #[redis(skip)]
+    rendered_html: Option<String>, // in-memory only
+    #[redis(skip, default = "fresh_hits")]
+    hits: u32,
+    #[redis(skip_serializing)]
+    views: u64, // maintained by HINCRBY, never overwritten
+}
+
+let article = Article { id: 7, rendered_html: Some("<p/>".into()), hits: 42, views: 3 };
+assert_eq!(article.to_redis_args(), vec![b"id".to_vec(), b"7".to_vec()]);
+
+let stored = redis::Value::Array(vec![
+    redis::Value::BulkString(b"id".to_vec()),
+    redis::Value::BulkString(b"7".to_vec()),
+    redis::Value::BulkString(b"views".to_vec()),
+    redis::Value::BulkString(b"10".to_vec()),
+]);
+let decoded = Article::from_redis_value(&stored).unwrap();
+assert_eq!(decoded, Article { id: 7, rendered_html: None, hits: 1, views: 10 });
+```
+
+### Attribute Validation
+
+`#[redis(...)]` attributes are checked at compile time. Unknown keys, keys given
+twice and invalid `rename_all` rules are reported as compile errors pointing at
+the offending attribute:
+
+```rust,compile_fail
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue)]
+#[redis(renam_all = "snake_case")] // error: unknown redis container attribute `renam_all`
+enum Status {
+    Active,
+}
+```
+
+Attributes that would have no effect where they are written are rejected the same
+way: variant settings such as `tag` or `strings` on a struct, key and persistence
+settings such as `repository` or `default` on an enum or tuple struct, `key_prefix`
+without `cluster_key`, `key_ttl_mode` without a TTL, and any field attribute on a
+tuple struct field, whose fields are written by position:
+
+```rust,compile_fail
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+struct Point(#[redis(skip)] u8, u8); // error: redis field attributes are not supported on tuple struct fields
+```
+
+A newtype variant of an internally tagged or untagged enum has no field of its own to
+store a scalar under, so `Wrap(u32)` would leave a lone value among the hash's
+field/value pairs:
+
+```rust,compile_fail
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+#[redis(tag = "type")]
+enum Event {
+    Wrap(u32), // error: a newtype variant of an internally tagged enum must wrap a type stored as fields
+}
+```
+
+The per-field helpers of `repository` are named after their field, so a field whose
+helper would repeat a generated method, such as `with_ttl` next to `save_with_ttl` or
+`cached` next to the `load_cached` of `cache`, is rejected as well:
+
+```rust,compile_fail
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(Clone, ToRedisArgs, FromRedisValue)]
+#[redis(key = "page:{id}", repository, cache)]
+struct Page {
+    id: u64,
+    cached: bool, // error: field `cached` would generate `load_cached`, which `cache` already generates
+}
+```
+
+```rust,compile_fail
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue)]
+#[redis(key = "order:{id}", repository)]
+struct Order {
+    id: u64,
+    with_ttl: bool, // error: field `with_ttl` would generate `save_with_ttl`, which `repository` already generates
+}
+```
+
 ### Redis Protocol Support
 
 This crate handles multiple Redis value types automatically:
@@ -160,7 +917,14 @@ This crate handles multiple Redis value types automatically:
 ### Advanced Features
 
 #### Hash Field Expiration (Redis 7.4+)
-```rust
+
+Fields with `#[redis(expire = "seconds")]` get a TTL through the generated
+`write_with_expiry` method, which queues the `HSET` followed by one
+`HEXPIRE key seconds FIELDS n field...` per distinct expiry. `write_with_expiry`,
+`write_with_ttl` and `KEY_TTL` are generated for types using `expire`, `expire_at`,
+`key_ttl` or `repository`:
+
+```rust,no_run
 use redis_derive::{FromRedisValue, ToRedisArgs};
 
 #[derive(ToRedisArgs, FromRedisValue)]
@@ -168,35 +932,421 @@ struct SessionData {
     user_id: u64,
     #[redis(expire = "1800")] // 30 minutes
     access_token: String,
-    #[redis(expire = "7200")] // 2 hours  
+    #[redis(expire = "7200")] // 2 hours
     refresh_token: String,
 }
+
+# fn main() -> redis::RedisResult<()> {
+# let client = redis::Client::open("redis://127.0.0.1/")?;
+# let mut con = client.get_connection()?;
+let session = SessionData {
+    user_id: 42,
+    access_token: "a1b2".to_string(),
+    refresh_token: "c3d4".to_string(),
+};
+
+let mut pipe = redis::pipe();
+pipe.atomic();
+session.write_with_expiry("session:42", &mut pipe);
+let _: () = pipe.query(&mut con)?;
+# Ok(())
+# }
+```
+
+The queued commands can be checked without a server by packing the pipeline. Fields
+sharing an expiry are grouped into one `HEXPIRE`, and omitted fields are left out:
+
+```rust
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+struct Session {
+    user_id: u64,
+    #[redis(expire = "1800")]
+    access_token: String,
+    #[redis(expire = "1800")]
+    csrf_token: String,
+    #[redis(expire = "1800")]
+    device: Option<String>,
+    #[redis(expire = "7200")]
+    refresh_token: String,
+}
+
+let session = Session {
+    user_id: 42,
+    access_token: "a1b2".to_string(),
+    csrf_token: "x9".to_string(),
+    device: None,
+    refresh_token: "c3d4".to_string(),
+};
+let mut pipe = redis::pipe();
+session.write_with_expiry("session:42", &mut pipe);
+
+let mut expected = redis::pipe();
+expected
+    .cmd("HSET")
+    .arg("session:42")
+    .arg(&["user_id", "42", "access_token", "a1b2", "csrf_token", "x9", "refresh_token", "c3d4"]);
+expected
+    .cmd("HEXPIRE")
+    .arg("session:42")
+    .arg(1800)
+    .arg(&["FIELDS", "2", "access_token", "csrf_token"]);
+expected
+    .cmd("HEXPIRE")
+    .arg("session:42")
+    .arg(7200)
+    .arg(&["FIELDS", "1", "refresh_token"]);
+assert_eq!(pipe.get_packed_pipeline(), expected.get_packed_pipeline());
+```
+
+Use `#[redis(expire_at = "field")]` to expire a hash field at the Unix timestamp (in
+seconds) held by a sibling field, queued as `HEXPIREAT key ts FIELDS 1 field`.
+`#[redis(expire_at_ms = "field")]` reads milliseconds and queues `HPEXPIREAT`.
+The sibling can be an integer, a `SystemTime`, or an `Option` of either (`None`
+queues nothing); it must be a stored field:
+
+```rust
+use redis_derive::ToRedisArgs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn unix_ms(time: &SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[derive(ToRedisArgs)]
+struct Offer {
+    #[redis(expire_at = "valid_until")]
+    price: u32,
+    valid_until: u64,
+    #[redis(expire_at_ms = "banner_until")]
+    banner: String,
+    #[redis(serialize_with = "unix_ms")]
+    banner_until: SystemTime,
+    #[redis(expire_at = "promo_until")]
+    promo: String,
+    promo_until: Option<u64>,
+}
+
+let offer = Offer {
+    price: 999,
+    valid_until: 1_900_000_000,
+    banner: "sale".to_string(),
+    banner_until: UNIX_EPOCH + Duration::from_millis(1_900_000_000_250),
+    promo: "spring".to_string(),
+    promo_until: None,
+};
+let mut pipe = redis::pipe();
+offer.write_with_expiry("offer:1", &mut pipe);
+
+let mut expected = redis::pipe();
+expected.cmd("HSET").arg("offer:1").arg(&[
+    "price", "999", "valid_until", "1900000000",
+    "banner", "sale", "banner_until", "1900000000250",
+    "promo", "spring",
+]);
+expected
+    .cmd("HEXPIREAT")
+    .arg("offer:1")
+    .arg(1_900_000_000)
+    .arg(&["FIELDS", "1", "price"]);
+expected
+    .cmd("HPEXPIREAT")
+    .arg("offer:1")
+    .arg(1_900_000_000_250_u64)
+    .arg(&["FIELDS", "1", "banner"]);
+// `promo_until` is `None`, so `promo` gets no `HEXPIREAT`.
+assert_eq!(pipe.get_packed_pipeline(), expected.get_packed_pipeline());
 ```
 
 #### Cluster-Aware Keys
+
+`#[redis(cluster_key = "field")]` generates `redis_key()`, `key_for(id)` and `slot()`.
+Keys wrap the field's value in a `{...}` hash tag after a prefix (the type name in
+snake_case, or `#[redis(key_prefix = "...")]`), so all keys of one entity map to the
+same cluster slot:
+
 ```rust
 use redis_derive::{FromRedisValue, ToRedisArgs};
 
 #[derive(ToRedisArgs, FromRedisValue)]
-#[redis(cluster_key = "user_id")]
+#[redis(cluster_key = "user_id", key_prefix = "user")]
 struct UserProfile {
     user_id: u64,
     profile_data: String,
 }
+
+let profile = UserProfile { user_id: 42, profile_data: "{}".to_string() };
+assert_eq!(profile.redis_key(), "user:{42}");
+assert_eq!(UserProfile::key_for(42), "user:{42}");
+assert_eq!(profile.slot(), 8000); // CLUSTER KEYSLOT user:{42}
+```
+
+`slot()` applies Redis's hash tag rule to the whole key: the tag ends at the first `}`,
+and a key with an empty tag is hashed in full:
+
+```rust
+use redis_derive::ToRedisArgs;
+
+#[derive(ToRedisArgs)]
+#[redis(cluster_key = "id", key_prefix = "cl")]
+struct Item {
+    id: String,
+}
+
+let empty = Item { id: String::new() };
+assert_eq!(empty.redis_key(), "cl:{}");
+assert_eq!(empty.slot(), 6163); // CLUSTER KEYSLOT cl:{}
+
+let nested = Item { id: "a}b".to_string() };
+assert_eq!(nested.redis_key(), "cl:{a}b}");
+assert_eq!(nested.slot(), 15495); // CLUSTER KEYSLOT a
 ```
 
-#### Client-Side Caching
+#### Key Templates
+
+`#[redis(key = "user:{id}")]` declares where a struct lives. Placeholders are checked
+against the struct's fields at compile time, and the derive generates `key(&self)`
+returning a `<Type>Key` newtype that implements `ToRedisArgs`, `FromRedisValue` and
+`Display`:
+
 ```rust
 use redis_derive::{FromRedisValue, ToRedisArgs};
 
 #[derive(ToRedisArgs, FromRedisValue)]
-#[redis(cache = true, ttl = "600")]
+#[redis(key = "user:{id}")]
+struct User {
+    id: u64,
+    name: String,
+}
+
+let user = User { id: 12345, name: "john".to_string() };
+assert_eq!(user.key().to_string(), "user:12345");
+assert_eq!(user.key(), UserKey::new(12345));
+```
+
+#### Repository Methods
+
+`#[redis(repository)]` generates `save`, `load`, `delete` and `exists` over any
+[`redis::ConnectionLike`]. The key comes from the `key` template (or `cluster_key`).
+`save` sends one `HSET key f1 v1 f2 v2 ...` in a `MULTI` block together with an
+`HDEL` of omitted fields and any field expiries. The type must derive both macros:
+
+```rust,no_run
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug)]
+#[redis(key = "user:{id}", repository)]
+struct User {
+    id: u64,
+    username: String,
+    email: Option<String>,
+}
+
+# fn main() -> redis::RedisResult<()> {
+# let client = redis::Client::open("redis://127.0.0.1/")?;
+# let mut con = client.get_connection()?;
+let user = User { id: 7, username: "jane".to_string(), email: None };
+user.save(&mut con)?;
+
+let loaded: Option<User> = User::load(&mut con, user.key())?;
+assert!(User::exists(&mut con, UserKey::new(7))?);
+assert!(User::delete(&mut con, user.key())?);
+# Ok(())
+# }
+```
+
+Each stored field also gets `load_<field>(con, key)` (an `HGET`) and
+`save_<field>(con, key, &value)` (an `HSET`, or `HDEL` for `None`). Since an `HSET`
+clears the field's TTL, `save_<field>` of an `expire` field queues its `HEXPIRE` in
+the same `MULTI` block, and that of an `expire_at` field takes the timestamp as an
+extra `expire_at` argument for the `HEXPIREAT`.
+
+With the `async` cargo feature every helper also has an `_async` counterpart over
+`redis::aio::ConnectionLike`, e.g. `MultiplexedConnection` or `ConnectionManager`.
+Enable a redis runtime such as `redis/tokio-comp` in your own `Cargo.toml`:
+
+```toml
+[dependencies]
+redis-derive = { version = "0.2.0", features = ["async"] }
+redis = { version = "0.32", features = ["tokio-comp"] }
+```
+
+```rust,no_run
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug)]
+#[redis(key = "user:{id}", repository)]
+struct User {
+    id: u64,
+    username: String,
+    email: Option<String>,
+}
+
+# #[cfg(not(feature = "async"))]
+# fn main() {}
+# #[cfg(feature = "async")]
+# #[tokio::main]
+# async fn main() -> redis::RedisResult<()> {
+# let client = redis::Client::open("redis://127.0.0.1/")?;
+let user = User { id: 7, username: "jane".to_string(), email: None };
+let mut con = client.get_multiplexed_async_connection().await?;
+user.save_async(&mut con).await?;
+let loaded = User::load_async(&mut con, user.key()).await?;
+let email = User::load_email_async(&mut con, user.key()).await?;
+# Ok(())
+# }
+```
+
+#### Key Expiration
+
+`#[redis(key_ttl = "seconds")]` (or `key_ttl_ms`) expires the whole hash after every
+write: `write_with_expiry` and `save` queue an `EXPIRE` (`PEXPIRE` for sub-second
+values) in the same `MULTI` block as the `HSET`. Without `cache`, `ttl = "seconds"`
+means the same thing. `key_ttl_mode = "NX" | "XX" | "GT" | "LT"` adds the matching
+condition flag (Redis 7.0+).
+
+The configured value is available as `KEY_TTL`; `write_with_ttl` and
+`save_with_ttl` take an `Option<Duration>` that overrides it for a single call. Since
+Redis deletes a key given a zero expiry, `key_ttl = "0"` is rejected and an override
+of `Duration::ZERO` leaves the key's expiry untouched, like `None`.
+
+```rust,no_run
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::time::Duration;
+
+#[derive(ToRedisArgs, FromRedisValue)]
+#[redis(key = "session:{id}", repository, key_ttl = "3600", key_ttl_mode = "GT")]
+struct Session {
+    id: u64,
+    user: String,
+}
+
+# fn main() -> redis::RedisResult<()> {
+# let client = redis::Client::open("redis://127.0.0.1/")?;
+# let mut con = client.get_connection()?;
+let session = Session { id: 1, user: "alice".to_string() };
+assert_eq!(Session::KEY_TTL, Some(Duration::from_secs(3600)));
+
+// MULTI, HSET session:1 ..., EXPIRE session:1 3600 GT, EXEC
+session.save(&mut con)?;
+// Remember-me sessions live for a week instead.
+session.save_with_ttl(&mut con, Some(Duration::from_secs(7 * 24 * 3600)))?;
+# Ok(())
+# }
+```
+
+`save_pipeline(ttl)` returns the `MULTI` block `save_with_ttl` runs, so the commands
+can be checked without a server:
+
+```rust
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::time::Duration;
+
+#[derive(ToRedisArgs, FromRedisValue)]
+#[redis(key = "session:{id}", repository, key_ttl = "3600")]
+struct Session {
+    id: u64,
+    user: String,
+    device: Option<String>,
+}
+
+let session = Session { id: 1, user: "alice".to_string(), device: None };
+
+let mut expected = redis::pipe();
+expected.atomic();
+expected.cmd("HDEL").arg("session:1").arg(&["device"]);
+expected.cmd("HSET").arg("session:1").arg(&["id", "1", "user", "alice"]);
+expected.cmd("EXPIRE").arg("session:1").arg(3600);
+let pipe = session.save_pipeline(Session::KEY_TTL);
+assert_eq!(pipe.get_packed_pipeline(), expected.get_packed_pipeline());
+
+let mut expected = redis::pipe();
+expected.atomic();
+expected.cmd("HDEL").arg("session:1").arg(&["device"]);
+expected.cmd("HSET").arg("session:1").arg(&["id", "1", "user", "alice"]);
+expected.cmd("PEXPIRE").arg("session:1").arg(1500);
+let pipe = session.save_pipeline(Some(Duration::from_millis(1500)));
+assert_eq!(pipe.get_packed_pipeline(), expected.get_packed_pipeline());
+
+let mut expected = redis::pipe();
+expected.atomic();
+expected.cmd("HDEL").arg("session:1").arg(&["device"]);
+expected.cmd("HSET").arg("session:1").arg(&["id", "1", "user", "alice"]);
+let pipe = session.save_pipeline(Some(Duration::ZERO));
+assert_eq!(pipe.get_packed_pipeline(), expected.get_packed_pipeline());
+```
+
+### Client-Side Caching
+
+`#[redis(cache)]` generates `load_cached(con, key)`, a `load` that keeps decoded
+values in an in-process cache keyed by the Redis key. With `ttl = "seconds"`
+entries are refetched once they are older than `ttl`. The type must be `Clone`.
+
+Entries are invalidated by Redis 6+ `CLIENT TRACKING`: open a RESP3 connection,
+register a push sender, call `enable_tracking(con)` and feed every push message to
+`handle_push`. `invalidate_cached(key)` and `clear_cache()` drop entries by hand.
+
+```rust,no_run
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(Clone, ToRedisArgs, FromRedisValue)]
+#[redis(key = "data:{id}", cache = true, ttl = "600")]
 struct CachedData {
     id: u64,
     data: String,
 }
+
+# fn main() -> redis::RedisResult<()> {
+let client = redis::Client::open("redis://127.0.0.1/?protocol=resp3")?;
+let mut con = client.get_connection()?;
+let (pushes, invalidations) = std::sync::mpsc::channel();
+con.set_push_sender(pushes);
+CachedData::enable_tracking(&mut con)?;
+
+let first = CachedData::load_cached(&mut con, CachedDataKey::new(1))?; // HGETALL
+let again = CachedData::load_cached(&mut con, CachedDataKey::new(1))?; // from memory
+
+for push in invalidations.try_iter() {
+    CachedData::handle_push(&push);
+}
+# Ok(())
+# }
 ```
 
+`handle_push` takes the `redis::PushInfo` values the connection forwards. An
+`invalidate` push lists the keys to drop; one carrying `Nil` follows a flush and, like
+a disconnection, drops every entry. Entries older than `ttl` are dropped on their next
+lookup. A load that overlaps an invalidation returns its reply without caching it, so
+a value Redis invalidated mid-fetch is not kept:
+
+```rust
+use redis::{PushInfo, PushKind, Value};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(Clone, ToRedisArgs, FromRedisValue)]
+#[redis(key = "data:{id}", cache = true, ttl = "600")]
+struct CachedData {
+    id: u64,
+    data: String,
+}
+
+// Another client changed `data:1`.
+CachedData::handle_push(&PushInfo {
+    kind: PushKind::Invalidate,
+    data: vec![Value::Array(vec![Value::BulkString(b"data:1".to_vec())])],
+});
+
+// FLUSHALL or FLUSHDB.
+CachedData::handle_push(&PushInfo { kind: PushKind::Invalidate, data: vec![Value::Nil] });
+
+// The connection dropped, and tracking with it.
+CachedData::handle_push(&PushInfo { kind: PushKind::Disconnection, data: vec![] });
+```
+
+With the `async` feature `load_cached_async` and `enable_tracking_async` work the
+same way over `redis::aio::ConnectionLike`.
+
 ## Development and Testing
 
 The crate includes comprehensive examples in the `examples/` directory:
@@ -213,11 +1363,13 @@ cargo run --example enum_branches
 
 # Debug attribute parsing behavior
 cargo run --example debug_attributes
+
+# Async repository helpers (requires the `async` feature)
+cargo run --example async_repository --features async
 ```
 
 ## Limitations
 
-- Only unit enums (variants without fields) are currently supported
 - Requires redis-rs 0.32.4 or later for full compatibility
 
 ## Compatibility
@@ -234,6 +1386,7 @@ use syn::{parse_macro_input, Data::*, DeriveInput};
 
 mod data_enum;
 mod data_struct;
+mod format;
 mod util;
 
 #[proc_macro_derive(ToRedisArgs, attributes(redis))]
@@ -244,11 +1397,41 @@ It generates efficient serialization code that converts Rust types to Redis argu
 # Attributes
 
 - `redis(rename_all = "...")`: Transform field/variant names using case conversion rules
+- `redis(skip)`: Neither write nor read the field; it is initialised with `Default::default()`
+- `redis(skip_serializing)` / `redis(skip_deserializing)`: Skip the field in one direction only
+- `redis(skip_serializing_if = "path")`: Omit the field whenever the predicate returns `true`
+- `redis(default)` / `redis(default = "path::to_fn")`: Value for a skipped or missing field; on the
+  struct, missing fields are taken from the struct's `Default` impl (or the given function)
+- `redis(flatten)` / `redis(flatten, prefix = "addr.")`: Store a nested struct's fields in the parent hash
+- `redis(json)`: Store a field as one JSON string (requires the `json` feature)
+- `redis(as_string)`: Write a field with `Display` and read it with `FromStr`
+- `redis(with = "module")`: Write and read a field with `module::write` / `module::read`
+- `redis(serialize_with = "path")` / `redis(deserialize_with = "path")`: Custom codec for one direction only
 - `redis(expire = "seconds")`: Set TTL for hash fields (requires Redis 7.4+)
-- `redis(expire_at = "field_name")`: Expire field at timestamp specified by another field
+- `redis(expire_at = "field_name")`: Expire field at the Unix timestamp (seconds) held by another field
+- `redis(expire_at_ms = "field_name")`: Same as `expire_at` with a millisecond timestamp (`HPEXPIREAT`)
 - `redis(cluster_key = "field_name")`: Use specified field for Redis Cluster hash tag generation
-- `redis(cache = true)`: Enable client-side caching support
-- `redis(ttl = "seconds")`: Default TTL for cached objects
+- `redis(key_prefix = "prefix")`: Prefix of the keys generated for `cluster_key` (defaults to the type name in snake_case)
+- `redis(key = "prefix:{field}")`: Key template generating `key()` and a typed `<Type>Key`
+- `redis(repository)`: Generate `save`, `load`, `delete` and `exists` helpers (needs `key` or `cluster_key`)
+- `redis(omitted_fields)`: Generate `omitted_fields()`, listing the fields a write leaves out (implied by `repository`)
+- `redis(cache = true)`: Generate `load_cached` backed by an in-process cache invalidated by `CLIENT TRACKING`
+- `redis(ttl = "seconds")`: Maximum age of cached objects; without `cache`, same as `key_ttl`
+- `redis(key_ttl = "seconds")` / `redis(key_ttl_ms = "ms")`: Expire the whole key on every save
+- `redis(key_ttl_mode = "NX|XX|GT|LT")`: Condition flag added to the key `EXPIRE`
+- `redis(tag = "field")`: Store enum variants as hashes with the variant name in `field`
+- `redis(content = "field")`: With `tag`, nest the variant's payload under `field`
+- `redis(repr = "int")`: Store a unit enum as its discriminant, typed by `#[repr(...)]`
+- `redis(strings)`: Generate `Display`, `FromStr`, `as_redis_str()` and `VARIANTS` for a unit enum
+- `redis(case_insensitive)` / `redis(trim)`: Match enum variant names ignoring ASCII case / surrounding whitespace
+- `redis(rename = "name")` on a variant: Name written and read for the variant
+- `redis(alias = "name")` on a variant: Extra name accepted when reading (repeatable)
+- `redis(value = 1)` / `redis(value = true)` on a unit variant: Integer or boolean written instead of the name
+- `redis(other)` on a variant: Catch-all for unknown values; a newtype variant keeps the raw value
+- `redis(untagged)`: Store only the variant's payload and try each variant when reading
+- `redis(format = "json|msgpack|bincode")`: Store the whole value as one serde-encoded argument (requires the feature of the same name)
+- `redis(as_string)` on a struct or enum: Store the whole value as its `Display` string, read back with `FromStr`
+- `redis(bound = "T: Trait, ...")`: Replace the inferred trait bounds on generic parameters
 
 ## Case Conversion Rules
 
@@ -258,17 +1441,20 @@ It generates efficient serialization code that converts Rust types to Redis argu
 - `"camelCase"`: `my_field` → `myField`
 - `"snake_case"`: `MyField` → `my_field`
 - `"kebab-case"`: `MyField` → `my-field`
+
+# Panics
+
+`ToRedisArgs` has no way to report an error, so the generated `write_redis_args` panics
+when serde fails to encode a `redis(json)` field or a `redis(format = "...")` value, for
+example a map whose keys are not strings in JSON. Use `serialize_with` to handle such
+failures yourself.
 */
 pub fn to_redis_args(tokenstream: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(tokenstream as DeriveInput);
-    let type_ident = ast.ident;
-    let attr_map = util::parse_attributes(&ast.attrs);
 
-    match ast.data {
-        Struct(data_struct) => data_struct::derive_to_redis_struct(data_struct, type_ident, attr_map),
-        Enum(data_enum) => data_enum::derive_to_redis_enum(data_enum, type_ident, attr_map),
-        Union(_) => panic!("ToRedisArgs cannot be derived for union types"),
-    }
+    derive_to_redis_args(ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 #[proc_macro_derive(FromRedisValue, attributes(redis))]
@@ -291,12 +1477,68 @@ The generated code provides detailed error messages including:
 */
 pub fn from_redis_value(tokenstream: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(tokenstream as DeriveInput);
+
+    derive_from_redis_value(ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_to_redis_args(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let type_ident = ast.ident;
+    let vis = ast.vis;
+    let generics = ast.generics;
+    let attr_map = util::parse_attributes(&ast.attrs)?;
+    util::check_container_target(&attr_map, &ast.data)?;
+
+    // Whole-value encodings replace the hash layout of structs and enums alike.
+    if !matches!(ast.data, Union(_)) {
+        if let Some(format) = attr_map.format {
+            return format::derive_to_redis_format(format, type_ident, generics, attr_map);
+        }
+        if attr_map.as_string {
+            return format::derive_to_redis_string(type_ident, generics, attr_map);
+        }
+    }
+
+    match ast.data {
+        Struct(data_struct) => {
+            data_struct::derive_to_redis_struct(data_struct, type_ident, vis, generics, attr_map)
+        }
+        Enum(data_enum) => {
+            data_enum::derive_to_redis_enum(data_enum, type_ident, generics, attr_map)
+        }
+        Union(data_union) => Err(syn::Error::new_spanned(
+            data_union.union_token,
+            "ToRedisArgs cannot be derived for union types",
+        )),
+    }
+}
+
+fn derive_from_redis_value(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let type_ident = ast.ident;
-    let attr_map = util::parse_attributes(&ast.attrs);
+    let generics = ast.generics;
+    let attr_map = util::parse_attributes(&ast.attrs)?;
+    util::check_container_target(&attr_map, &ast.data)?;
+
+    if !matches!(ast.data, Union(_)) {
+        if let Some(format) = attr_map.format {
+            return format::derive_from_redis_format(format, type_ident, generics, attr_map);
+        }
+        if attr_map.as_string {
+            return format::derive_from_redis_string(type_ident, generics, attr_map);
+        }
+    }
 
     match ast.data {
-        Struct(data_struct) => data_struct::derive_from_redis_struct(data_struct, type_ident, attr_map),
-        Enum(data_enum) => data_enum::derive_from_redis_enum(data_enum, type_ident, attr_map),
-        Union(_) => panic!("FromRedisValue cannot be derived for union types"),
+        Struct(data_struct) => {
+            data_struct::derive_from_redis_struct(data_struct, type_ident, generics, attr_map)
+        }
+        Enum(data_enum) => {
+            data_enum::derive_from_redis_enum(data_enum, type_ident, generics, attr_map)
+        }
+        Union(data_union) => Err(syn::Error::new_spanned(
+            data_union.union_token,
+            "FromRedisValue cannot be derived for union types",
+        )),
     }
-}
\ No newline at end of file
+}
@@ -1,112 +1,940 @@
-use syn::{Attribute, Meta};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::collections::HashMap;
+use syn::{
+    meta::ParseNestedMeta, parse_quote, punctuated::Punctuated, Attribute, Data, ExprPath, Fields,
+    GenericArgument, GenericParam, Generics, Ident, Lit, LitBool, LitStr, PathArguments, Token,
+    Type, WherePredicate,
+};
+
+/// Case conversion rules accepted by `#[redis(rename_all = "...")]`.
+pub const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "kebab-case",
+];
 
 #[derive(Debug, Default, Clone)]
 pub struct ParsedAttributeMap {
     pub rename_all: Option<String>,
-    pub cluster_key: Option<String>,
+    pub cluster_key: Option<Ident>,
+    pub key_prefix: Option<LitStr>,
+    pub key: Option<LitStr>,
+    pub repository: bool,
     pub cache: bool,
-    pub ttl: Option<String>,
+    /// `#[redis(omitted_fields)]`: generate `omitted_fields()` without `repository`.
+    pub omitted_fields: bool,
+    pub ttl: Option<u64>,
+    pub key_ttl: Option<KeyTtl>,
+    pub key_ttl_mode: Option<String>,
+    pub tag: Option<String>,
+    pub content: Option<String>,
+    pub untagged: bool,
+    pub case_insensitive: bool,
+    pub trim: bool,
+    /// Set by `#[redis(repr = "int")]` to the enum's `#[repr(...)]` integer type.
+    pub repr: Option<Ident>,
+    pub strings: bool,
+    pub format: Option<Format>,
+    /// `#[redis(as_string)]`: the whole value is one string written with `Display` and
+    /// read with `FromStr`.
+    pub as_string: bool,
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub default: Option<DefaultValue>,
+    /// Span of each attribute key given, for errors about combinations of them.
+    pub spans: HashMap<String, Span>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct FieldAttributes {
     pub skip: bool,
+    pub skip_serializing: bool,
+    pub skip_deserializing: bool,
+    pub skip_serializing_if: Option<ExprPath>,
+    pub default: Option<DefaultValue>,
     pub rename: Option<String>,
-    pub expire: Option<String>,
-    pub expire_at: Option<String>,
+    pub expire: Option<u64>,
+    pub expire_at: Option<ExpireAt>,
+    /// `#[redis(flatten)]`: store the field's own field/value pairs in the parent hash,
+    /// with names prefixed by `prefix`.
+    pub flatten: bool,
+    pub prefix: Option<String>,
+    /// `#[redis(json)]`: store the value as one JSON string (`json` feature).
+    pub json: bool,
+    /// `#[redis(as_string)]`: write the value with `Display` and read it with `FromStr`.
+    pub as_string: bool,
+    /// `#[redis(serialize_with = "path")]`, or `module::write` for `with = "module"`: a
+    /// `fn(&T) -> impl ToRedisArgs` producing the stored value.
+    pub serialize_with: Option<ExprPath>,
+    /// `#[redis(deserialize_with = "path")]`, or `module::read` for `with = "module"`: a
+    /// `fn(&redis::Value) -> RedisResult<T>` reading it back.
+    pub deserialize_with: Option<ExprPath>,
+    /// Span of each attribute key given, for errors about combinations of them.
+    pub spans: HashMap<String, Span>,
 }
 
-pub fn parse_attributes(attrs: &[Attribute]) -> ParsedAttributeMap {
-    let mut parsed = ParsedAttributeMap::default();
+#[derive(Debug, Default, Clone)]
+pub struct VariantAttributes {
+    pub rename: Option<String>,
+    pub aliases: Vec<String>,
+    pub other: bool,
+    pub value: Option<VariantValue>,
+}
 
-    for attr in attrs {
-        if !attr.path().is_ident("redis") {
-            continue;
+/// `#[redis(value = 1)]` / `#[redis(value = true)]`: the integer or boolean a unit
+/// variant is written as and read back from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariantValue {
+    Int(i64),
+    Bool(bool),
+}
+
+impl VariantValue {
+    /// The integer this value is compared as; booleans are `1` and `0`, as Redis
+    /// reports them over RESP2.
+    pub fn code(self) -> i64 {
+        match self {
+            VariantValue::Int(value) => value,
+            VariantValue::Bool(value) => i64::from(value),
         }
+    }
 
-        // Parse #[redis(...)] attributes
-        if let Meta::List(list) = &attr.meta {
-            // Convert token stream to string and parse manually for now
-            let tokens_str = list.tokens.to_string();
-            
-            // Look for rename_all = "value"
-            if let Some(rename_all_value) = extract_quoted_value(&tokens_str, "rename_all") {
-                parsed.rename_all = Some(rename_all_value);
-            }
-            
-            // Look for cluster_key = "value"  
-            if let Some(cluster_key_value) = extract_quoted_value(&tokens_str, "cluster_key") {
-                parsed.cluster_key = Some(cluster_key_value);
-            }
-            
-            // Look for ttl = "value"
-            if let Some(ttl_value) = extract_quoted_value(&tokens_str, "ttl") {
-                parsed.ttl = Some(ttl_value);
+    pub fn to_expr(self) -> TokenStream {
+        match self {
+            VariantValue::Int(value) => quote!(#value),
+            VariantValue::Bool(value) => quote!(#value),
+        }
+    }
+}
+
+/// `#[redis(expire_at = "field")]` / `#[redis(expire_at_ms = "field")]`: expire the
+/// hash field at the Unix timestamp held by a sibling field.
+#[derive(Debug, Clone)]
+pub struct ExpireAt {
+    pub field: Ident,
+    pub millis: bool,
+}
+
+/// `#[redis(key_ttl = "...")]` / `#[redis(key_ttl_ms = "...")]`, or `ttl` without
+/// `cache`: expire the whole key after each save.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyTtl {
+    pub value: u64,
+    pub millis: bool,
+}
+
+/// Condition flags accepted by `#[redis(key_ttl_mode = "...")]`.
+pub const KEY_TTL_MODES: &[&str] = &["NX", "XX", "GT", "LT"];
+
+/// `#[redis(format = "...")]`: store the whole value as one serde-encoded blob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    MessagePack,
+    Bincode,
+}
+
+/// Formats accepted by `#[redis(format = "...")]`, each behind the cargo feature of the
+/// same name.
+pub const FORMATS: &[&str] = &["json", "msgpack", "bincode"];
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "json" => Some(Format::Json),
+            "msgpack" => Some(Format::MessagePack),
+            "bincode" => Some(Format::Bincode),
+            _ => None,
+        }
+    }
+
+    fn enabled(self) -> bool {
+        match self {
+            Format::Json => cfg!(feature = "json"),
+            Format::MessagePack => cfg!(feature = "msgpack"),
+            Format::Bincode => cfg!(feature = "bincode"),
+        }
+    }
+}
+
+/// Representations accepted by `#[redis(repr = "...")]`.
+pub const REPRS: &[&str] = &["int"];
+
+/// Primitive integer types allowed in a Rust `#[repr(...)]` attribute.
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Value used for a field that is skipped or absent from the Redis reply.
+#[derive(Debug, Clone)]
+pub enum DefaultValue {
+    /// `#[redis(default)]`: `Default::default()`.
+    Trait,
+    /// `#[redis(default = "path::to_fn")]`: call the given function.
+    Function(ExprPath),
+}
+
+impl DefaultValue {
+    pub fn to_expr(&self) -> TokenStream {
+        match self {
+            DefaultValue::Trait => quote!(::core::default::Default::default()),
+            DefaultValue::Function(path) => quote!(#path()),
+        }
+    }
+}
+
+impl ParsedAttributeMap {
+    /// Span of the `key` attribute, see [`key_span`].
+    pub fn span(&self, key: &str) -> Span {
+        key_span(&self.spans, key)
+    }
+}
+
+impl FieldAttributes {
+    /// Span of the `key` attribute, see [`key_span`].
+    pub fn span(&self, key: &str) -> Span {
+        key_span(&self.spans, key)
+    }
+
+    /// Whether the field is left out of the generated `ToRedisArgs` output.
+    pub fn skips_serializing(&self) -> bool {
+        self.skip || self.skip_serializing
+    }
+
+    /// Whether the field is never read by the generated `FromRedisValue` impl.
+    pub fn skips_deserializing(&self) -> bool {
+        self.skip || self.skip_deserializing
+    }
+}
+
+/// Parse the container-level `#[redis(...)]` attributes of a struct or enum.
+pub fn parse_attributes(attrs: &[Attribute]) -> syn::Result<ParsedAttributeMap> {
+    let mut parsed = ParsedAttributeMap::default();
+    let mut seen = HashMap::new();
+
+    for attr in redis_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            let key = unique_key(&meta, &mut seen)?;
+
+            match key.as_str() {
+                "rename_all" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    if !RENAME_RULES.contains(&value.value().as_str()) {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            format!(
+                                "invalid rename_all value `{}`. Valid options: {}",
+                                value.value(),
+                                RENAME_RULES.join(", ")
+                            ),
+                        ));
+                    }
+                    parsed.rename_all = Some(value.value());
+                }
+                "cluster_key" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.cluster_key = Some(value.parse()?);
+                }
+                "key_prefix" => parsed.key_prefix = Some(meta.value()?.parse()?),
+                "key" => parsed.key = Some(meta.value()?.parse()?),
+                "repository" => parsed.repository = parse_flag(&meta)?,
+                "ttl" => parsed.ttl = Some(parse_positive_number(&meta, "seconds")?),
+                "cache" => parsed.cache = parse_flag(&meta)?,
+                "omitted_fields" => parsed.omitted_fields = parse_flag(&meta)?,
+                "key_ttl" | "key_ttl_ms" => {
+                    if parsed.key_ttl.is_some() {
+                        return Err(meta.error("`key_ttl` and `key_ttl_ms` cannot be combined"));
+                    }
+                    let millis = key == "key_ttl_ms";
+                    let unit = if millis { "milliseconds" } else { "seconds" };
+                    parsed.key_ttl = Some(KeyTtl {
+                        value: parse_positive_number(&meta, unit)?,
+                        millis,
+                    });
+                }
+                "key_ttl_mode" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    let mode = value.value().to_ascii_uppercase();
+                    if !KEY_TTL_MODES.contains(&mode.as_str()) {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            format!(
+                                "invalid key_ttl_mode value `{}`. Valid options: {}",
+                                value.value(),
+                                KEY_TTL_MODES.join(", ")
+                            ),
+                        ));
+                    }
+                    parsed.key_ttl_mode = Some(mode);
+                }
+                "tag" => parsed.tag = Some(parse_string(&meta)?),
+                "content" => parsed.content = Some(parse_string(&meta)?),
+                "untagged" => parsed.untagged = parse_flag(&meta)?,
+                "case_insensitive" => parsed.case_insensitive = parse_flag(&meta)?,
+                "trim" => parsed.trim = parse_flag(&meta)?,
+                "strings" => parsed.strings = parse_flag(&meta)?,
+                "format" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    let name = value.value();
+                    let format = Format::from_name(&name).ok_or_else(|| {
+                        syn::Error::new(
+                            value.span(),
+                            format!(
+                                "invalid format value `{name}`. Valid options: {}",
+                                FORMATS.join(", ")
+                            ),
+                        )
+                    })?;
+                    if !format.enabled() {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            format!("`format = \"{name}\"` requires the `{name}` feature of redis-derive"),
+                        ));
+                    }
+                    parsed.format = Some(format);
+                }
+                "as_string" => parsed.as_string = parse_flag(&meta)?,
+                "repr" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    if !REPRS.contains(&value.value().as_str()) {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            format!(
+                                "invalid repr value `{}`. Valid options: {}",
+                                value.value(),
+                                REPRS.join(", ")
+                            ),
+                        ));
+                    }
+                    parsed.repr = Some(int_repr(attrs)?);
+                }
+                "default" => parsed.default = Some(parse_default(&meta)?),
+                "bound" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.bound = Some(value.parse_with(Punctuated::parse_terminated)?);
+                }
+                _ => return Err(meta.error(format!("unknown redis container attribute `{key}`"))),
             }
-            
-            // Look for cache (boolean flag)
-            if tokens_str.contains("cache") {
-                parsed.cache = true;
+
+            Ok(())
+        })?;
+    }
+    parsed.spans = seen;
+
+    if parsed.content.is_some() && parsed.tag.is_none() {
+        return Err(syn::Error::new(
+            parsed.span("content"),
+            "`content` requires `tag`",
+        ));
+    }
+    if parsed.untagged && parsed.tag.is_some() {
+        return Err(syn::Error::new(
+            parsed.span("untagged"),
+            "`untagged` and `tag` cannot be combined",
+        ));
+    }
+    if let (Some(prefix), None) = (&parsed.key_prefix, &parsed.cluster_key) {
+        return Err(syn::Error::new(
+            prefix.span(),
+            "`key_prefix` requires `cluster_key`",
+        ));
+    }
+
+    // A value stored as one blob or string has no hash fields or variant names to configure.
+    let whole_value = match (parsed.format, parsed.as_string) {
+        (Some(_), true) => {
+            return Err(syn::Error::new(
+                parsed.span("as_string"),
+                "`format` cannot be combined with `as_string`",
+            ));
+        }
+        (Some(_), false) => Some("format"),
+        (None, true) => Some("as_string"),
+        (None, false) => None,
+    };
+    if let Some(whole_value) = whole_value {
+        let conflict = [
+            ("rename_all", parsed.rename_all.is_some()),
+            ("cluster_key", parsed.cluster_key.is_some()),
+            ("key", parsed.key.is_some()),
+            ("repository", parsed.repository),
+            ("cache", parsed.cache),
+            ("omitted_fields", parsed.omitted_fields),
+            ("ttl", parsed.ttl.is_some()),
+            ("key_ttl", parsed.key_ttl.is_some()),
+            ("tag", parsed.tag.is_some()),
+            ("untagged", parsed.untagged),
+            ("repr", parsed.repr.is_some()),
+            ("strings", parsed.strings),
+            ("case_insensitive", parsed.case_insensitive),
+            ("trim", parsed.trim),
+            ("default", parsed.default.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(name, set)| set.then_some(name));
+        if let Some(conflict) = conflict {
+            return Err(syn::Error::new(
+                parsed.span(conflict),
+                format!("`{whole_value}` cannot be combined with `{conflict}`"),
+            ));
+        }
+    }
+
+    // Without `cache`, `ttl` has nothing to bound but the key itself.
+    if let (Some(ttl), false) = (parsed.ttl, parsed.cache) {
+        if parsed.key_ttl.is_some() {
+            return Err(syn::Error::new(
+                parsed.span("key_ttl"),
+                "`ttl` and `key_ttl` cannot be combined without `cache`",
+            ));
+        }
+        parsed.key_ttl = Some(KeyTtl {
+            value: ttl,
+            millis: false,
+        });
+    }
+    if parsed.key_ttl_mode.is_some() && parsed.key_ttl.is_none() {
+        return Err(syn::Error::new(
+            parsed.span("key_ttl_mode"),
+            "`key_ttl_mode` requires `key_ttl` (or `ttl` without `cache`)",
+        ));
+    }
+
+    Ok(parsed)
+}
+
+/// Reject container attributes that have no effect on the kind of type being derived:
+/// hash and persistence settings need a struct with named fields, variant settings an
+/// enum.
+pub fn check_container_target(parsed: &ParsedAttributeMap, data: &Data) -> syn::Result<()> {
+    let hash_only = [
+        ("cluster_key", parsed.cluster_key.is_some()),
+        ("key_prefix", parsed.key_prefix.is_some()),
+        ("key", parsed.key.is_some()),
+        ("repository", parsed.repository),
+        ("cache", parsed.cache),
+        ("omitted_fields", parsed.omitted_fields),
+        ("ttl", parsed.ttl.is_some()),
+        ("key_ttl", parsed.key_ttl.is_some()),
+        ("key_ttl_mode", parsed.key_ttl_mode.is_some()),
+        ("default", parsed.default.is_some()),
+    ];
+    let enum_only = [
+        ("tag", parsed.tag.is_some()),
+        ("content", parsed.content.is_some()),
+        ("untagged", parsed.untagged),
+        ("case_insensitive", parsed.case_insensitive),
+        ("trim", parsed.trim),
+        ("repr", parsed.repr.is_some()),
+        ("strings", parsed.strings),
+    ];
+
+    let named = match data {
+        Data::Struct(data_struct) => {
+            if let Some((name, _)) = enum_only.into_iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    parsed.span(name),
+                    format!("`{name}` requires an enum"),
+                ));
             }
+            matches!(data_struct.fields, Fields::Named(_))
         }
+        Data::Enum(_) => false,
+        Data::Union(_) => return Ok(()),
+    };
+    if named {
+        return Ok(());
     }
 
-    parsed
+    // `rename_all` still renames the variants of an enum.
+    let rename_all = matches!(data, Data::Struct(_)) && parsed.rename_all.is_some();
+    let misplaced = hash_only
+        .into_iter()
+        .chain([("rename_all", rename_all)])
+        .find(|(_, set)| *set);
+    match misplaced {
+        Some((name, _)) => Err(syn::Error::new(
+            parsed.span(name),
+            format!("`{name}` requires a struct with named fields"),
+        )),
+        None => Ok(()),
+    }
 }
 
-pub fn parse_field_attributes(attrs: &[Attribute]) -> FieldAttributes {
+/// Parse the `#[redis(...)]` attributes of a single named field.
+pub fn parse_field_attributes(attrs: &[Attribute]) -> syn::Result<FieldAttributes> {
     let mut field_attrs = FieldAttributes::default();
+    let mut seen = HashMap::new();
+    let mut codec = None;
+
+    for attr in redis_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            let key = unique_key(&meta, &mut seen)?;
+
+            match key.as_str() {
+                "flatten" => field_attrs.flatten = parse_flag(&meta)?,
+                "prefix" => field_attrs.prefix = Some(parse_string(&meta)?),
+                "json" => {
+                    if !cfg!(feature = "json") {
+                        return Err(
+                            meta.error("`json` requires the `json` feature of redis-derive")
+                        );
+                    }
+                    field_attrs.json = parse_flag(&meta)?;
+                }
+                "as_string" => field_attrs.as_string = parse_flag(&meta)?,
+                "with" | "serialize_with" | "deserialize_with" => {
+                    // `with` fills both sides, so a second codec attribute always overlaps it.
+                    if codec.is_some() && seen.contains_key("with") {
+                        return Err(meta.error(
+                            "`with` cannot be combined with `serialize_with` or `deserialize_with`",
+                        ));
+                    }
+                    let value: LitStr = meta.value()?.parse()?;
+                    let path: ExprPath = value.parse()?;
+                    match key.as_str() {
+                        "with" => {
+                            field_attrs.serialize_with = Some(parse_quote!(#path::write));
+                            field_attrs.deserialize_with = Some(parse_quote!(#path::read));
+                        }
+                        "serialize_with" => field_attrs.serialize_with = Some(path),
+                        _ => field_attrs.deserialize_with = Some(path),
+                    }
+                    codec.get_or_insert(key);
+                }
+                "skip" => field_attrs.skip = parse_flag(&meta)?,
+                "skip_serializing" => field_attrs.skip_serializing = parse_flag(&meta)?,
+                "skip_deserializing" => field_attrs.skip_deserializing = parse_flag(&meta)?,
+                "skip_serializing_if" => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    field_attrs.skip_serializing_if = Some(value.parse()?);
+                }
+                "default" => field_attrs.default = Some(parse_default(&meta)?),
+                "rename" => field_attrs.rename = Some(parse_string(&meta)?),
+                "expire" => field_attrs.expire = Some(parse_positive_number(&meta, "seconds")?),
+                "expire_at" | "expire_at_ms" => {
+                    if field_attrs.expire_at.is_some() {
+                        return Err(meta.error("`expire_at` and `expire_at_ms` cannot be combined"));
+                    }
+                    let value: LitStr = meta.value()?.parse()?;
+                    field_attrs.expire_at = Some(ExpireAt {
+                        field: value.parse()?,
+                        millis: key == "expire_at_ms",
+                    });
+                }
+                _ => return Err(meta.error(format!("unknown redis field attribute `{key}`"))),
+            }
+
+            Ok(())
+        })?;
+    }
+    field_attrs.spans = seen;
+
+    if let (Some(_), Some(expire_at)) = (field_attrs.expire, &field_attrs.expire_at) {
+        return Err(syn::Error::new(
+            expire_at.field.span(),
+            "`expire` and `expire_at` cannot be combined on one field",
+        ));
+    }
 
-    for attr in attrs {
-        if !attr.path().is_ident("redis") {
-            continue;
+    // `json`, `as_string` and a custom codec each replace the field's redis traits.
+    let mut codecs = Vec::new();
+    if field_attrs.json {
+        codecs.push("json");
+    }
+    if field_attrs.as_string {
+        codecs.push("as_string");
+    }
+    codecs.extend(codec.as_deref());
+    if let [first, second, ..] = codecs[..] {
+        return Err(syn::Error::new(
+            field_attrs.span(second),
+            format!("`{first}` cannot be combined with `{second}`"),
+        ));
+    }
+
+    if field_attrs.flatten {
+        let conflict = if field_attrs.rename.is_some() {
+            Some("rename")
+        } else if field_attrs.expire.is_some() {
+            Some("expire")
+        } else if field_attrs.expire_at.is_some() {
+            Some("expire_at")
+        } else {
+            codecs.first().copied()
+        };
+        if let Some(conflict) = conflict {
+            return Err(syn::Error::new(
+                field_attrs.span(conflict),
+                format!("`flatten` cannot be combined with `{conflict}`"),
+            ));
         }
+    } else if field_attrs.prefix.is_some() {
+        return Err(syn::Error::new(
+            field_attrs.span("prefix"),
+            "`prefix` requires `flatten`",
+        ));
+    }
+
+    Ok(field_attrs)
+}
 
-        if let Meta::List(list) = &attr.meta {
-            let tokens_str = list.tokens.to_string();
-            
-            if tokens_str.contains("skip") {
-                field_attrs.skip = true;
+/// Parse the `#[redis(...)]` attributes of an enum variant.
+pub fn parse_variant_attributes(attrs: &[Attribute]) -> syn::Result<VariantAttributes> {
+    let mut variant_attrs = VariantAttributes::default();
+    let mut seen = HashMap::new();
+
+    for attr in redis_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            // `alias` may be given any number of times.
+            if meta.path.is_ident("alias") {
+                variant_attrs.aliases.push(parse_string(&meta)?);
+                return Ok(());
+            }
+
+            let key = unique_key(&meta, &mut seen)?;
+            match key.as_str() {
+                "rename" => variant_attrs.rename = Some(parse_string(&meta)?),
+                "other" => variant_attrs.other = parse_flag(&meta)?,
+                "value" => variant_attrs.value = Some(parse_variant_value(&meta)?),
+                _ => return Err(meta.error(format!("unknown redis variant attribute `{key}`"))),
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(variant_attrs)
+}
+
+/// A piece of a `#[redis(key = "...")]` template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySegment {
+    Literal(String),
+    Field(String),
+}
+
+/// Split a key template such as `user:{id}` into literal text and `{field}`
+/// placeholders. `{{` and `}}` stand for literal braces.
+pub fn parse_key_template(template: &str) -> Result<Vec<KeySegment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
             }
-            
-            if let Some(rename_value) = extract_quoted_value(&tokens_str, "rename") {
-                field_attrs.rename = Some(rename_value);
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
             }
-            
-            if let Some(expire_value) = extract_quoted_value(&tokens_str, "expire") {
-                // Make sure it's not expire_at
-                if !tokens_str.contains("expire_at") {
-                    field_attrs.expire = Some(expire_value);
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(format!("unclosed `{{` in key template `{template}`")),
+                    }
                 }
+                if field.is_empty() {
+                    return Err(format!("empty placeholder in key template `{template}`"));
+                }
+                if !literal.is_empty() {
+                    segments.push(KeySegment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(KeySegment::Field(field));
+            }
+            '}' => return Err(format!("unmatched `}}` in key template `{template}`")),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(KeySegment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// Whether `ty` is spelled as `Option<...>` (optionally path-qualified).
+pub fn is_option(ty: &Type) -> bool {
+    option_inner(ty).is_some()
+}
+
+/// The `T` of a type spelled as `Option<T>`.
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident != "Option" {
+                return None;
             }
-            
-            if let Some(expire_at_value) = extract_quoted_value(&tokens_str, "expire_at") {
-                field_attrs.expire_at = Some(expire_at_value);
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first()? {
+                    GenericArgument::Type(inner) => Some(inner),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        Type::Group(group) => option_inner(&group.elem),
+        Type::Paren(paren) => option_inner(&paren.elem),
+        _ => None,
+    }
+}
+
+/// Whether `ty` is spelled as `SystemTime` (optionally path-qualified).
+pub fn is_system_time(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "SystemTime"),
+        Type::Group(group) => is_system_time(&group.elem),
+        Type::Paren(paren) => is_system_time(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Whether `ty` is a primitive, `String` or `str` (or a reference to or `Option` of one),
+/// which writes a single value rather than field/value pairs.
+pub fn is_scalar(ty: &Type) -> bool {
+    const SCALARS: &[&str] = &[
+        "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+        "i128", "isize", "f32", "f64", "String", "str",
+    ];
+    if let Some(inner) = option_inner(ty) {
+        return is_scalar(inner);
+    }
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| SCALARS.iter().any(|scalar| segment.ident == scalar)),
+        Type::Reference(reference) => is_scalar(&reference.elem),
+        Type::Group(group) => is_scalar(&group.elem),
+        Type::Paren(paren) => is_scalar(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Add `trait_bound` to every type parameter mentioned by one of `field_types`.
+///
+/// When the container carries `#[redis(bound = "...")]` those predicates are used
+/// verbatim instead of the inferred ones.
+pub fn add_trait_bounds(
+    mut generics: Generics,
+    field_types: &[&Type],
+    trait_bound: TokenStream,
+    custom_bound: Option<&Punctuated<WherePredicate, Token![,]>>,
+) -> Generics {
+    let predicates: Vec<WherePredicate> = match custom_bound {
+        Some(custom) => custom.iter().cloned().collect(),
+        None => generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(type_param) => Some(&type_param.ident),
+                _ => None,
+            })
+            .filter(|ident| {
+                field_types
+                    .iter()
+                    .any(|ty| mentions_ident(ty.to_token_stream(), ident))
+            })
+            .map(|ident| parse_quote!(#ident: #trait_bound))
+            .collect(),
+    };
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Bound each of `types` that mentions a type parameter by `Display` or, with `parse`,
+/// by `FromStr` with a displayable error, for values stored with `as_string`.
+pub fn string_bounds(mut generics: Generics, types: &[&Type], parse: bool) -> Generics {
+    let params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let predicates: Vec<WherePredicate> = types
+        .iter()
+        .filter(|ty| {
+            params
+                .iter()
+                .any(|ident| mentions_ident(ty.to_token_stream(), ident))
+        })
+        .flat_map(|ty| -> Vec<WherePredicate> {
+            if parse {
+                vec![
+                    parse_quote!(#ty: std::str::FromStr),
+                    parse_quote!(<#ty as std::str::FromStr>::Err: std::fmt::Display),
+                ]
+            } else {
+                vec![parse_quote!(#ty: std::fmt::Display)]
             }
+        })
+        .collect();
+
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// Expression naming the `redis::Value` variant of `value`, for error messages.
+pub fn value_kind(value: TokenStream) -> TokenStream {
+    quote! {
+        match #value {
+            redis::Value::Nil => "Nil",
+            redis::Value::Int(_) => "Int",
+            redis::Value::BulkString(_) => "BulkString",
+            redis::Value::Array(_) => "Array",
+            redis::Value::SimpleString(_) => "SimpleString",
+            redis::Value::Okay => "Okay",
+            redis::Value::Map(_) => "Map",
+            redis::Value::Attribute { .. } => "Attribute",
+            redis::Value::Set(_) => "Set",
+            redis::Value::Double(_) => "Double",
+            redis::Value::Boolean(_) => "Boolean",
+            redis::Value::VerbatimString { .. } => "VerbatimString",
+            redis::Value::BigNumber(_) => "BigNumber",
+            redis::Value::Push { .. } => "Push",
+            redis::Value::ServerError(_) => "ServerError",
         }
     }
+}
 
-    field_attrs
+fn mentions_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(candidate) => candidate == *ident,
+        TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        _ => false,
+    })
 }
 
-/// Extract a quoted string value from tokens like: key = "value"
-fn extract_quoted_value(tokens: &str, key: &str) -> Option<String> {
-    // Look for pattern: key = "value"
-    let pattern = format!("{key} =");
-    if let Some(start_pos) = tokens.find(&pattern) {
-        let after_equals = &tokens[start_pos + pattern.len()..];
-        
-        // Find the opening quote
-        if let Some(quote_start) = after_equals.find('"') {
-            let after_quote = &after_equals[quote_start + 1..];
-            
-            // Find the closing quote
-            if let Some(quote_end) = after_quote.find('"') {
-                return Some(after_quote[..quote_end].to_string());
+/// Find the integer type of `#[repr(u8)]` and friends, defaulting to `isize` like rustc.
+fn int_repr(attrs: &[Attribute]) -> syn::Result<Ident> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let items = attr.parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)?;
+        for item in items {
+            if let Some(ident) = item.path().get_ident() {
+                if INT_REPRS.contains(&ident.to_string().as_str()) {
+                    return Ok(ident.clone());
+                }
             }
         }
     }
-    None
+    Ok(Ident::new("isize", Span::call_site()))
+}
+
+fn redis_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("redis"))
+}
+
+/// Return the key of a nested meta item, rejecting keys that were already given.
+fn unique_key(meta: &ParseNestedMeta, seen: &mut HashMap<String, Span>) -> syn::Result<String> {
+    let ident = meta
+        .path
+        .get_ident()
+        .ok_or_else(|| meta.error("expected a plain identifier as redis attribute key"))?;
+    let key = ident.to_string();
+
+    if seen.insert(key.clone(), ident.span()).is_some() {
+        return Err(meta.error(format!("duplicate redis attribute `{key}`")));
+    }
+
+    Ok(key)
+}
+
+/// Span of the attribute `key` (or its `_ms` form) in `spans`, or the call site if it
+/// was not given.
+fn key_span(spans: &HashMap<String, Span>, key: &str) -> Span {
+    spans
+        .get(key)
+        .or_else(|| spans.get(&format!("{key}_ms")))
+        .copied()
+        .unwrap_or_else(Span::call_site)
+}
+
+/// Parse `key = "value"`.
+fn parse_string(meta: &ParseNestedMeta) -> syn::Result<String> {
+    let value: LitStr = meta.value()?.parse()?;
+    Ok(value.value())
+}
+
+/// Parse `key = "123"` into a whole number of `unit`s.
+fn parse_whole_number(meta: &ParseNestedMeta, unit: &str) -> syn::Result<u64> {
+    let value: LitStr = meta.value()?.parse()?;
+    value.value().parse().map_err(|_| {
+        syn::Error::new(
+            value.span(),
+            format!(
+                "expected a whole number of {unit}, found `{}`",
+                value.value()
+            ),
+        )
+    })
+}
+
+/// Like `parse_whole_number`, for durations: Redis deletes a key or field given a zero
+/// expiry instead of keeping it, and a zero cache `ttl` would never serve an entry.
+fn parse_positive_number(meta: &ParseNestedMeta, unit: &str) -> syn::Result<u64> {
+    match parse_whole_number(meta, unit)? {
+        0 => Err(meta.error(format!("expected a positive number of {unit}, found `0`"))),
+        value => Ok(value),
+    }
+}
+
+/// Parse `value = 1`, `value = -1` or `value = true`.
+fn parse_variant_value(meta: &ParseNestedMeta) -> syn::Result<VariantValue> {
+    let input = meta.value()?;
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    match input.parse::<Lit>()? {
+        Lit::Int(value) => {
+            let digits = if negative {
+                format!("-{}", value.base10_digits())
+            } else {
+                value.base10_digits().to_string()
+            };
+            digits
+                .parse()
+                .map(VariantValue::Int)
+                .map_err(|_| syn::Error::new(value.span(), "value does not fit in an i64"))
+        }
+        Lit::Bool(value) if !negative => Ok(VariantValue::Bool(value.value)),
+        other => Err(syn::Error::new(
+            other.span(),
+            "expected an integer or boolean literal",
+        )),
+    }
+}
+
+/// Parse either a bare `default` or `default = "path::to_fn"`.
+fn parse_default(meta: &ParseNestedMeta) -> syn::Result<DefaultValue> {
+    if meta.input.peek(Token![=]) {
+        let value: LitStr = meta.value()?.parse()?;
+        Ok(DefaultValue::Function(value.parse()?))
+    } else {
+        Ok(DefaultValue::Trait)
+    }
+}
+
+/// Parse either a bare `key` or `key = true|false`.
+fn parse_flag(meta: &ParseNestedMeta) -> syn::Result<bool> {
+    if meta.input.peek(syn::Token![=]) {
+        let value: LitBool = meta.value()?.parse()?;
+        Ok(value.value)
+    } else {
+        Ok(true)
+    }
 }
 
 pub fn transform_variant_name(variant_name: &str, rename_all: Option<&String>) -> String {
@@ -122,11 +950,7 @@ pub fn transform_variant_name(variant_name: &str, rename_all: Option<&String>) -
         "camelCase" => to_camel_case(variant_name),
         "snake_case" => to_snake_case(variant_name),
         "kebab-case" => to_kebab_case(variant_name),
-        _ => {
-            panic!(
-                "Invalid rename_all value: {rename_rule}. Valid options: lowercase, UPPERCASE, PascalCase, camelCase, snake_case, kebab-case"
-            );
-        }
+        _ => unreachable!("rename_all is validated by parse_attributes"),
     }
 }
 
@@ -194,11 +1018,10 @@ fn to_kebab_case(s: &str) -> String {
     to_snake_case(s).replace('_', "-")
 }
 
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use syn::parse::Parser;
 
     #[test]
     fn test_case_transformations() {
@@ -226,15 +1049,598 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_quoted_value() {
+    fn test_parse_attributes() {
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(rename_all = "snake_case", cache = true)]
+            #[redis(ttl = "600")]
+            struct Cached;
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert_eq!(parsed.rename_all.as_deref(), Some("snake_case"));
+        assert_eq!(parsed.ttl, Some(600));
+        assert!(parsed.cache);
+        assert!(parsed.key_ttl.is_none());
+        assert!(parsed.cluster_key.is_none());
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(ttl = "3600", key_ttl_mode = "nx")]
+            struct Expiring;
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
         assert_eq!(
-            extract_quoted_value(r#"rename_all = "snake_case""#, "rename_all"),
-            Some("snake_case".to_string())
+            parsed.key_ttl,
+            Some(KeyTtl {
+                value: 3600,
+                millis: false
+            })
         );
+        assert_eq!(parsed.key_ttl_mode.as_deref(), Some("NX"));
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(tag = "type", content = "data")]
+            enum Adjacent { A(u8) }
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert_eq!(parsed.tag.as_deref(), Some("type"));
+        assert_eq!(parsed.content.as_deref(), Some("data"));
+        assert!(!parsed.untagged);
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(case_insensitive, trim = false, strings)]
+            enum Lenient { A }
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert!(parsed.case_insensitive);
+        assert!(!parsed.trim);
+        assert!(parsed.strings);
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[derive(Clone, Copy)]
+            #[repr(C, u16)]
+            #[redis(repr = "int")]
+            enum Code { A = 1 }
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert_eq!(parsed.repr.unwrap(), "u16");
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(repr = "int")]
+            enum Implicit { A }
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert_eq!(parsed.repr.unwrap(), "isize");
+        assert!(parse_attributes(&[]).unwrap().repr.is_none());
+
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            #[redis(cache, ttl = "60", key_ttl_ms = "1500")]
+            struct Both;
+        };
+        let parsed = parse_attributes(&ast.attrs).unwrap();
+        assert_eq!(parsed.ttl, Some(60));
         assert_eq!(
-            extract_quoted_value(r#"expire = "3600""#, "expire"),
-            Some("3600".to_string())
+            parsed.key_ttl,
+            Some(KeyTtl {
+                value: 1500,
+                millis: true
+            })
         );
-        assert_eq!(extract_quoted_value("cache", "cache"), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_field_attributes() {
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(rename = "cache_key", expire_at = "valid_until")]
+                value: String
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert_eq!(parsed.rename.as_deref(), Some("cache_key"));
+        let expire_at = parsed.expire_at.unwrap();
+        assert_eq!(expire_at.field, "valid_until");
+        assert!(!expire_at.millis);
+        assert_eq!(parsed.expire, None);
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(expire = "1800")]
+                token: String
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert_eq!(parsed.expire, Some(1800));
+        assert!(!parsed.skip);
+        assert!(!parsed.flatten);
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(flatten, prefix = "addr.")]
+                address: Address
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert!(parsed.flatten);
+        assert_eq!(parsed.prefix.as_deref(), Some("addr."));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(prefix = "addr.")]
+                address: Address
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err.to_string().contains("`prefix` requires `flatten`"));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(flatten, rename = "addr")]
+                address: Address
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`flatten` cannot be combined with `rename`"));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(json)]
+                tags: Vec<String>
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs);
+        if cfg!(feature = "json") {
+            assert!(parsed.unwrap().json);
+        } else {
+            let err = parsed.unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("`json` requires the `json` feature of redis-derive"));
+        }
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(with = "unix_time")]
+                created: SystemTime
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        let path = |path: Option<ExprPath>| path.unwrap().to_token_stream().to_string();
+        assert_eq!(path(parsed.serialize_with), "unix_time :: write");
+        assert_eq!(path(parsed.deserialize_with), "unix_time :: read");
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(deserialize_with = "codec::read_lenient")]
+                created: SystemTime
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert!(parsed.serialize_with.is_none());
+        assert_eq!(path(parsed.deserialize_with), "codec :: read_lenient");
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(serialize_with = "codec::write", with = "unix_time")]
+                created: SystemTime
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`with` cannot be combined with `serialize_with` or `deserialize_with`"));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(as_string)]
+                ip: Option<Ipv4Addr>
+            })
+            .unwrap();
+        assert!(parse_field_attributes(&field.attrs).unwrap().as_string);
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(as_string, with = "unix_time")]
+                created: SystemTime
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`as_string` cannot be combined with `with`"));
+    }
+
+    #[test]
+    fn test_parse_variant_attributes() {
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(rename = "pro", alias = "premium")]
+            #[redis(alias = "gold")]
+            Professional
+        };
+        let parsed = parse_variant_attributes(&variant.attrs).unwrap();
+        assert_eq!(parsed.rename.as_deref(), Some("pro"));
+        assert_eq!(parsed.aliases, ["premium", "gold"]);
+        assert!(!parsed.other);
+        assert!(parsed.value.is_none());
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(value = -1)]
+            Disabled
+        };
+        let parsed = parse_variant_attributes(&variant.attrs).unwrap();
+        assert_eq!(parsed.value, Some(VariantValue::Int(-1)));
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(value = true)]
+            On
+        };
+        let value = parse_variant_attributes(&variant.attrs).unwrap().value;
+        assert_eq!(value.map(|value| value.code()), Some(1));
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(value = "on")]
+            Quoted
+        };
+        let err = parse_variant_attributes(&variant.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected an integer or boolean literal"));
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(other)]
+            Unknown(String)
+        };
+        assert!(parse_variant_attributes(&variant.attrs).unwrap().other);
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(rename = "a", rename = "b")]
+            Twice
+        };
+        let err = parse_variant_attributes(&variant.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("duplicate redis attribute `rename`"));
+
+        let variant: syn::Variant = syn::parse_quote! {
+            #[redis(skip)]
+            Skipped
+        };
+        let err = parse_variant_attributes(&variant.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unknown redis variant attribute `skip`"));
+    }
+
+    #[test]
+    fn test_parse_skip_attributes() {
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(skip, default = "crate::defaults::hits")]
+                hits: u32
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert!(parsed.skips_serializing() && parsed.skips_deserializing());
+        assert_eq!(
+            parsed.default.unwrap().to_expr().to_string(),
+            "crate :: defaults :: hits ()"
+        );
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(skip_serializing)]
+                views: u64
+            })
+            .unwrap();
+        let parsed = parse_field_attributes(&field.attrs).unwrap();
+        assert!(parsed.skips_serializing() && !parsed.skips_deserializing());
+    }
+
+    #[test]
+    fn test_parse_key_template() {
+        assert_eq!(
+            parse_key_template("user:{id}:{{meta}}").unwrap(),
+            vec![
+                KeySegment::Literal("user:".to_string()),
+                KeySegment::Field("id".to_string()),
+                KeySegment::Literal(":{meta}".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_key_template("{org}{id}").unwrap(),
+            vec![
+                KeySegment::Field("org".to_string()),
+                KeySegment::Field("id".to_string()),
+            ]
+        );
+        assert!(parse_key_template("user:{id").is_err());
+        assert!(parse_key_template("user:id}").is_err());
+        assert!(parse_key_template("user:{}").is_err());
+    }
+
+    #[test]
+    fn test_is_option() {
+        assert!(is_option(&syn::parse_quote!(Option<String>)));
+        assert!(is_option(&syn::parse_quote!(std::option::Option<u64>)));
+        assert!(!is_option(&syn::parse_quote!(Vec<Option<u8>>)));
+        assert!(!is_option(&syn::parse_quote!(String)));
+
+        let optional_time: Type = syn::parse_quote!(Option<std::time::SystemTime>);
+        assert!(is_system_time(option_inner(&optional_time).unwrap()));
+        assert!(!is_system_time(&syn::parse_quote!(u64)));
+    }
+
+    #[test]
+    fn test_is_scalar() {
+        assert!(is_scalar(&syn::parse_quote!(u32)));
+        assert!(is_scalar(&syn::parse_quote!(std::string::String)));
+        assert!(is_scalar(&syn::parse_quote!(&'static str)));
+        assert!(is_scalar(&syn::parse_quote!(Option<bool>)));
+        assert!(!is_scalar(&syn::parse_quote!(Address)));
+        assert!(!is_scalar(&syn::parse_quote!(Option<Address>)));
+        assert!(!is_scalar(&syn::parse_quote!(HashMap<String, String>)));
+    }
+
+    #[test]
+    fn test_add_trait_bounds() {
+        let ast: syn::DeriveInput = syn::parse_quote! {
+            struct Page<'a, T, U, const N: usize> where U: Clone {
+                items: Vec<T>,
+                cursor: &'a str,
+                marker: std::marker::PhantomData<U>,
+            }
+        };
+        let syn::Data::Struct(data) = &ast.data else {
+            unreachable!()
+        };
+        let field_types: Vec<&Type> = data.fields.iter().take(2).map(|f| &f.ty).collect();
+
+        let generics = add_trait_bounds(
+            ast.generics.clone(),
+            &field_types,
+            quote::quote!(redis::ToRedisArgs),
+            None,
+        );
+        let where_clause = generics.where_clause.to_token_stream().to_string();
+        assert_eq!(where_clause, "where U : Clone , T : redis :: ToRedisArgs");
+
+        let custom: Punctuated<WherePredicate, Token![,]> = syn::parse_quote!(T: Copy);
+        let generics = add_trait_bounds(
+            ast.generics,
+            &field_types,
+            quote::quote!(redis::ToRedisArgs),
+            Some(&custom),
+        );
+        let where_clause = generics.where_clause.to_token_stream().to_string();
+        assert_eq!(where_clause, "where U : Clone , T : Copy");
+    }
+
+    #[test]
+    fn test_parse_attributes_errors() {
+        let unknown: syn::DeriveInput = syn::parse_quote! {
+            #[redis(renam_all = "snake_case")]
+            struct Typo;
+        };
+        let err = parse_attributes(&unknown.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unknown redis container attribute `renam_all`"));
+
+        let duplicate: syn::DeriveInput = syn::parse_quote! {
+            #[redis(ttl = "1")]
+            #[redis(ttl = "2")]
+            struct Twice;
+        };
+        let err = parse_attributes(&duplicate.attrs).unwrap_err();
+        assert!(err.to_string().contains("duplicate redis attribute `ttl`"));
+
+        let repr: syn::DeriveInput = syn::parse_quote! {
+            #[redis(repr = "string")]
+            enum Named { A }
+        };
+        let err = parse_attributes(&repr.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid repr value `string`. Valid options: int"));
+
+        let format: syn::DeriveInput = syn::parse_quote! {
+            #[redis(format = "yaml")]
+            struct Blob;
+        };
+        let err = parse_attributes(&format.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid format value `yaml`. Valid options: json, msgpack, bincode"));
+
+        let format: syn::DeriveInput = syn::parse_quote! {
+            #[redis(format = "json", key = "blob:{id}")]
+            struct Blob { id: u64 }
+        };
+        let err = parse_attributes(&format.attrs).unwrap_err().to_string();
+        if cfg!(feature = "json") {
+            assert!(err.contains("`format` cannot be combined with `key`"));
+        } else {
+            assert!(err.contains("`format = \"json\"` requires the `json` feature of redis-derive"));
+        }
+
+        let as_string: syn::DeriveInput = syn::parse_quote! {
+            #[redis(as_string)]
+            struct Version { major: u32, minor: u32 }
+        };
+        assert!(parse_attributes(&as_string.attrs).unwrap().as_string);
+
+        let as_string: syn::DeriveInput = syn::parse_quote! {
+            #[redis(as_string, repository)]
+            struct Version { major: u32, minor: u32 }
+        };
+        let err = parse_attributes(&as_string.attrs).unwrap_err().to_string();
+        assert!(err.contains("`as_string` cannot be combined with `repository`"));
+
+        let invalid: syn::DeriveInput = syn::parse_quote! {
+            #[redis(rename_all = "SCREAMING")]
+            struct Loud;
+        };
+        let err = parse_attributes(&invalid.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid rename_all value `SCREAMING`"));
+
+        let conflicting: syn::DeriveInput = syn::parse_quote! {
+            #[redis(ttl = "60", key_ttl = "3600")]
+            struct Conflicting;
+        };
+        let err = parse_attributes(&conflicting.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`ttl` and `key_ttl` cannot be combined without `cache`"));
+
+        let content: syn::DeriveInput = syn::parse_quote! {
+            #[redis(content = "c")]
+            enum Untagged { A(u8) }
+        };
+        let err = parse_attributes(&content.attrs).unwrap_err();
+        assert!(err.to_string().contains("`content` requires `tag`"));
+
+        let tagged: syn::DeriveInput = syn::parse_quote! {
+            #[redis(tag = "t", untagged)]
+            enum Both { A(u8) }
+        };
+        let err = parse_attributes(&tagged.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`untagged` and `tag` cannot be combined"));
+
+        let mode: syn::DeriveInput = syn::parse_quote! {
+            #[redis(key_ttl = "60", key_ttl_mode = "always")]
+            struct Mode;
+        };
+        let err = parse_attributes(&mode.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid key_ttl_mode value `always`. Valid options: NX, XX, GT, LT"));
+
+        let ttl: syn::DeriveInput = syn::parse_quote! {
+            #[redis(cache, ttl = "10m")]
+            struct Cached;
+        };
+        let err = parse_attributes(&ttl.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a whole number of seconds, found `10m`"));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(expire = "30m")]
+                token: String
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a whole number of seconds, found `30m`"));
+
+        let field: syn::Field = syn::Field::parse_named
+            .parse2(quote::quote! {
+                #[redis(expire = "0")]
+                token: String
+            })
+            .unwrap();
+        let err = parse_field_attributes(&field.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a positive number of seconds, found `0`"));
+
+        let prefix: syn::DeriveInput = syn::parse_quote! {
+            #[redis(key_prefix = "user")]
+            struct User { id: u64 }
+        };
+        let err = parse_attributes(&prefix.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`key_prefix` requires `cluster_key`"));
+
+        let mode: syn::DeriveInput = syn::parse_quote! {
+            #[redis(cache, ttl = "60", key_ttl_mode = "NX")]
+            struct Mode { id: u64 }
+        };
+        let err = parse_attributes(&mode.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`key_ttl_mode` requires `key_ttl` (or `ttl` without `cache`)"));
+
+        let zero: syn::DeriveInput = syn::parse_quote! {
+            #[redis(key_ttl_ms = "0")]
+            struct Zero { id: u64 }
+        };
+        let err = parse_attributes(&zero.attrs).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a positive number of milliseconds, found `0`"));
+    }
+
+    #[test]
+    fn test_check_container_target() {
+        let check = |input: syn::DeriveInput| {
+            let parsed = parse_attributes(&input.attrs).unwrap();
+            check_container_target(&parsed, &input.data).map_err(|err| err.to_string())
+        };
+
+        for (input, error) in [
+            (
+                syn::parse_quote! {
+                    #[redis(strings)]
+                    struct User { id: u64 }
+                },
+                "`strings` requires an enum",
+            ),
+            (
+                syn::parse_quote! {
+                    #[redis(tag = "type")]
+                    struct User { id: u64 }
+                },
+                "`tag` requires an enum",
+            ),
+            (
+                syn::parse_quote! {
+                    #[redis(repository, key = "status:{id}")]
+                    enum Status { Active }
+                },
+                "`key` requires a struct with named fields",
+            ),
+            (
+                syn::parse_quote! {
+                    #[redis(default)]
+                    enum Status { Active }
+                },
+                "`default` requires a struct with named fields",
+            ),
+            (
+                syn::parse_quote! {
+                    #[redis(key_ttl = "60")]
+                    struct Pair(u64, u64);
+                },
+                "`key_ttl` requires a struct with named fields",
+            ),
+            (
+                syn::parse_quote! {
+                    #[redis(rename_all = "snake_case")]
+                    struct Pair(u64, u64);
+                },
+                "`rename_all` requires a struct with named fields",
+            ),
+        ] {
+            assert!(check(input).unwrap_err().contains(error), "{error}");
+        }
+
+        check(syn::parse_quote! {
+            #[redis(rename_all = "snake_case", case_insensitive)]
+            enum Status { Active }
+        })
+        .unwrap();
+        check(syn::parse_quote! {
+            #[redis(key = "user:{id}", repository, default)]
+            struct User { id: u64 }
+        })
+        .unwrap();
+    }
+}
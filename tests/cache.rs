@@ -0,0 +1,186 @@
+//! `#[redis(cache)]` loaders, run against a fake connection that counts the
+//! `HGETALL`s reaching it.
+
+use redis::{ConnectionLike, ErrorKind, PushInfo, PushKind, RedisError, RedisResult, Value};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+use std::time::Duration;
+
+/// Replies to every command with a hash of `id` and `data`, and runs `during_fetch`
+/// before replying.
+struct FakeConnection {
+    fetches: usize,
+    during_fetch: fn(),
+}
+
+impl FakeConnection {
+    fn new() -> Self {
+        FakeConnection {
+            fetches: 0,
+            during_fetch: || {},
+        }
+    }
+}
+
+impl ConnectionLike for FakeConnection {
+    fn req_packed_command(&mut self, _cmd: &[u8]) -> RedisResult<Value> {
+        self.fetches += 1;
+        (self.during_fetch)();
+        Ok(Value::Array(vec![
+            Value::BulkString(b"id".to_vec()),
+            Value::BulkString(b"1".to_vec()),
+            Value::BulkString(b"data".to_vec()),
+            Value::BulkString(b"fresh".to_vec()),
+        ]))
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        _cmd: &[u8],
+        _offset: usize,
+        _count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        Err(RedisError::from((
+            ErrorKind::ClientError,
+            "the cache loaders send single commands",
+        )))
+    }
+
+    fn get_db(&self) -> i64 {
+        0
+    }
+
+    fn check_connection(&mut self) -> bool {
+        true
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+fn invalidate(key: &str) -> PushInfo {
+    PushInfo {
+        kind: PushKind::Invalidate,
+        data: vec![Value::Array(vec![Value::BulkString(
+            key.as_bytes().to_vec(),
+        )])],
+    }
+}
+
+// Every test has its own type, and with it its own cache.
+
+#[derive(Clone, Debug, PartialEq, ToRedisArgs, FromRedisValue)]
+#[redis(key = "hits:{id}", cache = true)]
+struct Hits {
+    id: u64,
+    data: String,
+}
+
+#[derive(Clone, Debug, PartialEq, ToRedisArgs, FromRedisValue)]
+#[redis(key = "pushes:{id}", cache = true)]
+struct Pushes {
+    id: u64,
+    data: String,
+}
+
+#[derive(Clone, Debug, PartialEq, ToRedisArgs, FromRedisValue)]
+#[redis(key = "race:{id}", cache = true)]
+struct Race {
+    id: u64,
+    data: String,
+}
+
+#[derive(Clone, Debug, PartialEq, ToRedisArgs, FromRedisValue)]
+#[redis(key = "aging:{id}", cache = true, ttl = "1")]
+struct Aging {
+    id: u64,
+    data: String,
+}
+
+#[test]
+fn serves_repeated_loads_from_memory() {
+    let mut con = FakeConnection::new();
+    let first = Hits::load_cached(&mut con, HitsKey::new(1)).unwrap();
+    let again = Hits::load_cached(&mut con, HitsKey::new(1)).unwrap();
+
+    assert_eq!(
+        first,
+        Some(Hits {
+            id: 1,
+            data: "fresh".to_string()
+        })
+    );
+    assert_eq!(again, first);
+    assert_eq!(con.fetches, 1);
+
+    Hits::invalidate_cached(HitsKey::new(1));
+    Hits::load_cached(&mut con, HitsKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 2);
+
+    Hits::clear_cache();
+    Hits::load_cached(&mut con, HitsKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 3);
+}
+
+#[test]
+fn drops_entries_named_by_invalidation_pushes() {
+    let mut con = FakeConnection::new();
+    Pushes::load_cached(&mut con, PushesKey::new(1)).unwrap();
+    Pushes::load_cached(&mut con, PushesKey::new(2)).unwrap();
+    assert_eq!(con.fetches, 2);
+
+    // Pushes that are not invalidations leave the cache alone.
+    Pushes::handle_push(&PushInfo {
+        kind: PushKind::Message,
+        data: vec![],
+    });
+    Pushes::handle_push(&invalidate("pushes:1"));
+    Pushes::load_cached(&mut con, PushesKey::new(1)).unwrap();
+    Pushes::load_cached(&mut con, PushesKey::new(2)).unwrap();
+    assert_eq!(con.fetches, 3);
+
+    // A flush drops everything.
+    Pushes::handle_push(&PushInfo {
+        kind: PushKind::Invalidate,
+        data: vec![Value::Nil],
+    });
+    Pushes::load_cached(&mut con, PushesKey::new(1)).unwrap();
+    Pushes::load_cached(&mut con, PushesKey::new(2)).unwrap();
+    assert_eq!(con.fetches, 5);
+
+    // So does losing the connection, and tracking with it.
+    Pushes::handle_push(&PushInfo {
+        kind: PushKind::Disconnection,
+        data: vec![],
+    });
+    Pushes::load_cached(&mut con, PushesKey::new(2)).unwrap();
+    assert_eq!(con.fetches, 6);
+}
+
+#[test]
+fn does_not_cache_a_reply_invalidated_mid_fetch() {
+    let mut con = FakeConnection {
+        fetches: 0,
+        during_fetch: || Race::handle_push(&invalidate("race:1")),
+    };
+    Race::load_cached(&mut con, RaceKey::new(1)).unwrap();
+    Race::load_cached(&mut con, RaceKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 2);
+
+    con.during_fetch = || {};
+    Race::load_cached(&mut con, RaceKey::new(1)).unwrap();
+    Race::load_cached(&mut con, RaceKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 3);
+}
+
+#[test]
+fn refetches_entries_older_than_ttl() {
+    let mut con = FakeConnection::new();
+    Aging::load_cached(&mut con, AgingKey::new(1)).unwrap();
+    Aging::load_cached(&mut con, AgingKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 1);
+
+    std::thread::sleep(Duration::from_millis(1100));
+    Aging::load_cached(&mut con, AgingKey::new(1)).unwrap();
+    assert_eq!(con.fetches, 2);
+}
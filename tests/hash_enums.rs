@@ -0,0 +1,132 @@
+//! Round trips of enums stored as hashes through their redis args.
+
+use redis::{FromRedisValue, ToRedisArgs, Value};
+use redis_derive::{FromRedisValue, ToRedisArgs};
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case")]
+enum Event {
+    Login {
+        user: Option<String>,
+        ip: Option<String>,
+    },
+    Maybe(Option<u32>),
+    Heartbeat,
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+struct Circle {
+    radius: u32,
+    x: Option<i32>,
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(rename_all = "snake_case")]
+enum Shape {
+    Circle(Circle),
+    Square { side: u32 },
+}
+
+#[derive(ToRedisArgs, FromRedisValue, Debug, PartialEq)]
+#[redis(tag = "t", content = "c")]
+enum Adjacent {
+    Circle(Circle),
+    Label(String),
+}
+
+fn stored(value: &impl ToRedisArgs) -> Value {
+    Value::Array(
+        value
+            .to_redis_args()
+            .into_iter()
+            .map(Value::BulkString)
+            .collect(),
+    )
+}
+
+fn round_trip<T>(value: T)
+where
+    T: ToRedisArgs + FromRedisValue + std::fmt::Debug + PartialEq,
+{
+    let stored_value = stored(&value);
+    assert_eq!(
+        value.num_of_args(),
+        value.to_redis_args().len(),
+        "{value:?}"
+    );
+    assert_eq!(T::from_redis_value(&stored_value).unwrap(), value);
+}
+
+#[test]
+fn marks_externally_tagged_variants_with_an_empty_payload() {
+    let login = Event::Login {
+        user: None,
+        ip: None,
+    };
+    assert_eq!(
+        login.to_redis_args(),
+        vec![b"login.".to_vec(), b"".to_vec()]
+    );
+    round_trip(login);
+
+    let maybe = Event::Maybe(None);
+    assert_eq!(
+        maybe.to_redis_args(),
+        vec![b"maybe.".to_vec(), b"".to_vec()]
+    );
+    round_trip(maybe);
+}
+
+#[test]
+fn writes_no_marker_next_to_a_payload() {
+    let login = Event::Login {
+        user: None,
+        ip: Some("10.0.0.1".to_string()),
+    };
+    assert_eq!(
+        login.to_redis_args(),
+        vec![b"login.ip".to_vec(), b"10.0.0.1".to_vec()]
+    );
+    round_trip(login);
+
+    let maybe = Event::Maybe(Some(7));
+    assert_eq!(
+        maybe.to_redis_args(),
+        vec![b"maybe".to_vec(), b"7".to_vec()]
+    );
+    round_trip(maybe);
+
+    round_trip(Event::Heartbeat);
+}
+
+#[test]
+fn nests_the_fields_of_a_struct_newtype_payload() {
+    let circle = Shape::Circle(Circle {
+        radius: 1,
+        x: Some(2),
+    });
+    assert_eq!(
+        circle.to_redis_args(),
+        vec![
+            b"circle.radius".to_vec(),
+            b"1".to_vec(),
+            b"circle.x".to_vec(),
+            b"2".to_vec()
+        ]
+    );
+    round_trip(circle);
+    round_trip(Shape::Square { side: 3 });
+
+    let adjacent = Adjacent::Circle(Circle { radius: 1, x: None });
+    assert_eq!(
+        adjacent.to_redis_args(),
+        vec![
+            b"t".to_vec(),
+            b"Circle".to_vec(),
+            b"c.radius".to_vec(),
+            b"1".to_vec()
+        ]
+    );
+    round_trip(adjacent);
+    round_trip(Adjacent::Label("round".to_string()));
+}